@@ -1,79 +1,222 @@
+mod multiplayer;
+mod rules;
+mod scorecard;
+
+pub use multiplayer::Match;
+pub use rules::{Rules, MAX_FRAMES};
+pub use scorecard::FrameScore;
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     NotEnoughPinsLeft,
     GameComplete,
+    /// A game needs at least one frame and at most [MAX_FRAMES]
+    InvalidFrameCount(usize),
+    /// No-tap needs a strike count between one pin and the full rack
+    InvalidNoTapCount(u16),
+    /// A match needs at least one player
+    NoPlayers,
+    /// Each player in a match needs a different name
+    DuplicatePlayer(String),
+}
+
+const PINS: u16 = 10;
+
+/// One ball as it was bowled
+#[derive(Debug, Clone, Copy)]
+struct Ball {
+    pins: u16,
+    /// Bowled at a full rack
+    fresh_rack: bool,
+    /// Left no pins standing, or was a no-tap strike
+    cleared: bool,
 }
 
 #[derive(Debug)]
 struct Frame {
-    first: u16,
-    second: Option<u16>,
+    rolls: Vec<u16>,
+    /// The last frame of the game, which takes the fill balls
+    last: bool,
+    rules: Rules,
 }
 
 impl Frame {
+    fn new(rules: Rules, last: bool) -> Self {
+        Frame {
+            rolls: Vec::new(),
+            last,
+            rules,
+        }
+    }
+
+    /// Every ball so far, along with the pins left standing after the last one.
+    /// The rack is reset whenever it is cleared, which only happens in the last frame
+    /// since clearing it ends any other frame.
+    fn balls(&self) -> (Vec<Ball>, u16) {
+        let mut standing = PINS;
+        let mut fresh_rack = true;
+        let balls = self
+            .rolls
+            .iter()
+            .map(|&pins| {
+                let cleared = pins == standing || (fresh_rack && pins >= self.rules.strike_at);
+                let ball = Ball {
+                    pins,
+                    fresh_rack,
+                    cleared,
+                };
+                standing -= pins;
+                fresh_rack = cleared;
+                if cleared {
+                    standing = PINS;
+                }
+                ball
+            })
+            .collect();
+        (balls, standing)
+    }
+
+    /// Which ball of the frame proper cleared the rack, if any did
+    fn cleared_by(&self) -> Option<usize> {
+        let (balls, _) = self.balls();
+        balls
+            .iter()
+            .take(self.rules.balls)
+            .position(|ball| ball.cleared)
+    }
+
     fn complete(&self) -> bool {
-        self.strike() || self.second.is_some()
+        let frame_balls = self.cleared_by().map_or(self.rules.balls, |i| i + 1);
+        let fill_balls = if self.last { self.bonus_balls() } else { 0 };
+        self.rolls.len() >= frame_balls + fill_balls
     }
 
     fn strike(&self) -> bool {
-        self.first == 10
+        self.cleared_by() == Some(0)
     }
 
+    /// Cleared by the second ball. Clearing it with a later ball earns no bonus.
     fn spare(&self) -> bool {
-        self.first + self.second.unwrap_or_default() == 10
+        self.cleared_by() == Some(1)
+    }
+
+    /// How many of the following rolls count towards this frame
+    fn bonus_balls(&self) -> usize {
+        if self.strike() {
+            2
+        } else if self.spare() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Pins standing for the next roll. In the last frame the pins are reset after
+    /// every strike or spare so the fill balls have a full rack.
+    fn pins_left(&self) -> u16 {
+        self.balls().1
+    }
+
+    /// What each roll is worth, a no-tap strike counting as the full rack
+    fn values(&self) -> Vec<u16> {
+        let (balls, _) = self.balls();
+        balls
+            .iter()
+            .map(|ball| {
+                if ball.cleared && ball.fresh_rack {
+                    PINS
+                } else {
+                    ball.pins
+                }
+            })
+            .collect()
+    }
+
+    /// How each roll is written on a scorecard: `X` for a strike, `/` for a spare,
+    /// `-` for no pins and the pin count otherwise
+    fn marks(&self) -> String {
+        let (balls, _) = self.balls();
+        balls
+            .iter()
+            .map(|ball| {
+                if ball.cleared && ball.fresh_rack {
+                    'X'
+                } else if ball.cleared {
+                    '/'
+                } else if ball.pins == 0 {
+                    '-'
+                } else {
+                    std::char::from_digit(ball.pins.into(), 10).unwrap()
+                }
+            })
+            .collect()
     }
 
     fn score_spare(&self, next: u16) -> u16 {
-        10 + next
+        PINS + next
     }
 
     fn score_strike(&self, first: u16, second: u16) -> u16 {
-        10 + first + second
+        PINS + first + second
     }
 
     fn score_open(&self) -> u16 {
-        self.first + self.second.unwrap_or_default()
+        self.values().iter().sum()
     }
 }
 
 pub struct BowlingGame {
     scorecard: Vec<Frame>,
+    rules: Rules,
+}
+
+impl Default for BowlingGame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BowlingGame {
+    /// A ten pin game
     pub fn new() -> Self {
         Self {
             scorecard: Default::default(),
+            rules: Rules::default(),
         }
     }
 
+    /// A game bowled under other rules
+    pub fn with_rules(rules: Rules) -> Result<Self, Error> {
+        rules.validate()?;
+        Ok(Self {
+            scorecard: Default::default(),
+            rules,
+        })
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
     pub fn roll(&mut self, pins: u16) -> Result<(), Error> {
         if self.complete() {
             return Err(Error::GameComplete);
         }
 
-        if let Some(frame) = self.scorecard.last() {
-            if frame.complete() {
-                self.roll_new_frame(pins)?;
-            } else {
-                self.roll_incomplete_frame(pins)?;
-            }
-        } else {
-            self.roll_new_frame(pins)?;
+        match self.scorecard.last() {
+            Some(frame) if !frame.complete() => self.roll_incomplete_frame(pins),
+            _ => self.roll_new_frame(pins),
         }
-
-        Ok(())
     }
 
     fn roll_new_frame(&mut self, pins: u16) -> Result<(), Error> {
-        match pins {
-            (0..=10) => self.scorecard.push(Frame {
-                first: pins,
-                second: None,
-            }),
-            _ => return Err(Error::NotEnoughPinsLeft),
-        };
-
+        if pins > PINS {
+            return Err(Error::NotEnoughPinsLeft);
+        }
+        let last = self.scorecard.len() == self.rules.frames - 1;
+        let mut frame = Frame::new(self.rules, last);
+        frame.rolls.push(pins);
+        self.scorecard.push(frame);
         Ok(())
     }
 
@@ -83,39 +226,56 @@ impl BowlingGame {
             .last_mut()
             .expect("This function requires an incomplete frame in the scorecard");
 
-        if pins <= 10 - frame.first {
-            frame.second = Some(pins);
-        } else {
+        if pins > frame.pins_left() {
             return Err(Error::NotEnoughPinsLeft);
         }
+        frame.rolls.push(pins);
         Ok(())
     }
 
-    pub fn complete(&self) -> bool {
-
+    /// Takes back the last roll, returning the pins it knocked down
+    pub fn undo(&mut self) -> Option<u16> {
+        let frame = self.scorecard.last_mut()?;
+        let pins = frame.rolls.pop();
+        if frame.rolls.is_empty() {
+            self.scorecard.pop();
+        }
+        pins
+    }
 
+    /// True once every frame has been rolled, including any fill balls
+    pub fn complete(&self) -> bool {
+        self.scorecard.len() == self.rules.frames
+            && self.scorecard.last().is_some_and(Frame::complete)
+    }
 
-        // short circuit evaluation will prevent scorecard[9] from ever indexing out of bounds
-        self.scorecard.len() == 10 && self.scorecard[9].complete()
+    /// Number of frames finished so far
+    pub(crate) fn frames_complete(&self) -> usize {
+        self.scorecard.iter().filter(|f| f.complete()).count()
     }
 
+    /// The total once the game is complete
     pub fn score(&self) -> Option<u16> {
         if !self.complete() {
             return None;
         }
+        (0..self.rules.frames).map(|i| self.frame_score(i)).sum()
+    }
 
-        let mut score = 0;
-        for i in 0..10 {
-            let f = &self.scorecard[i];
-            if f.spare() {
-                let next = self.scorecard.get(i + 1).map_or(0, |f| f.first);
-                score += f.score_spare(next);
-            } else {
-                score += f.score_open()
-            }
-
+    /// Points for frame `i` on its own, once its rolls and any bonus rolls are in.
+    /// A strike scores the next two rolls as a bonus and a spare the next one, while
+    /// the last frame just scores its pins.
+    fn frame_score(&self, i: usize) -> Option<u16> {
+        let f = self.scorecard.get(i).filter(|f| f.complete())?;
+        let mut next = self.scorecard[i + 1..].iter().flat_map(Frame::values);
+        if f.last {
+            Some(f.score_open())
+        } else if f.strike() {
+            Some(f.score_strike(next.next()?, next.next()?))
+        } else if f.spare() {
+            Some(f.score_spare(next.next()?))
+        } else {
+            Some(f.score_open())
         }
-
-        Some(score)
     }
 }
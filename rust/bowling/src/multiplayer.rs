@@ -0,0 +1,85 @@
+//! Several bowlers taking turns at a frame each.
+
+use crate::{BowlingGame, Error, Rules};
+
+/// A game per player, bowled in turn: each player bowls a whole frame, fill balls
+/// included, before the next player steps up.
+pub struct Match {
+    players: Vec<(String, BowlingGame)>,
+    /// Which player bowled each roll, oldest first
+    history: Vec<usize>,
+}
+
+impl Match {
+    /// A ten pin match between `players`, who bowl in the order given
+    pub fn new(players: &[&str]) -> Result<Self, Error> {
+        Self::with_rules(players, Rules::default())
+    }
+
+    pub fn with_rules(players: &[&str], rules: Rules) -> Result<Self, Error> {
+        if players.is_empty() {
+            return Err(Error::NoPlayers);
+        }
+        for (i, name) in players.iter().enumerate() {
+            if players[..i].contains(name) {
+                return Err(Error::DuplicatePlayer(name.to_string()));
+            }
+        }
+        let players = players
+            .iter()
+            .map(|name| Ok((name.to_string(), BowlingGame::with_rules(rules)?)))
+            .collect::<Result<_, Error>>()?;
+        Ok(Self {
+            players,
+            history: Vec::new(),
+        })
+    }
+
+    /// Whoever has finished the fewest frames, earliest in the order on a tie.
+    /// `None` once everyone has finished.
+    fn current(&self) -> Option<usize> {
+        if self.complete() {
+            return None;
+        }
+        (0..self.players.len()).min_by_key(|&i| self.players[i].1.frames_complete())
+    }
+
+    /// The player up next
+    pub fn current_player(&self) -> Option<&str> {
+        self.current().map(|i| self.players[i].0.as_str())
+    }
+
+    /// Rolls for the current player
+    pub fn roll(&mut self, pins: u16) -> Result<(), Error> {
+        let i = self.current().ok_or(Error::GameComplete)?;
+        self.players[i].1.roll(pins)?;
+        self.history.push(i);
+        Ok(())
+    }
+
+    /// Takes back the last roll of the match, whoever bowled it, returning the pins it
+    /// knocked down. That player is up again afterwards.
+    pub fn undo(&mut self) -> Option<u16> {
+        let i = self.history.pop()?;
+        self.players[i].1.undo()
+    }
+
+    pub fn complete(&self) -> bool {
+        self.players.iter().all(|(_, game)| game.complete())
+    }
+
+    pub fn game(&self, player: &str) -> Option<&BowlingGame> {
+        self.players
+            .iter()
+            .find(|(name, _)| name == player)
+            .map(|(_, game)| game)
+    }
+
+    /// Every player's final score, in playing order
+    pub fn scores(&self) -> Vec<(&str, Option<u16>)> {
+        self.players
+            .iter()
+            .map(|(name, game)| (name.as_str(), game.score()))
+            .collect()
+    }
+}
@@ -0,0 +1,82 @@
+//! The rule variants a game can be bowled under.
+
+use crate::{Error, PINS};
+
+/// The most frames a game can have, so that a perfect game of three strikes a frame
+/// still has a score that fits in a `u16`
+pub const MAX_FRAMES: usize = (u16::MAX / (3 * PINS)) as usize;
+
+/// How many balls a frame has, how many frames a game has and how many pins count as
+/// a strike. Every variant uses a rack of ten pins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub(crate) balls: usize,
+    pub(crate) frames: usize,
+    pub(crate) strike_at: u16,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::ten_pin()
+    }
+}
+
+impl Rules {
+    /// Two balls a frame over ten frames
+    pub fn ten_pin() -> Self {
+        Rules {
+            balls: 2,
+            frames: 10,
+            strike_at: PINS,
+        }
+    }
+
+    /// Three balls a frame. Clearing the rack with the third ball scores ten with no
+    /// bonus.
+    pub fn candlepin() -> Self {
+        Rules {
+            balls: 3,
+            ..Rules::ten_pin()
+        }
+    }
+
+    /// Scored the same way as candlepin
+    pub fn duckpin() -> Self {
+        Rules::candlepin()
+    }
+
+    /// Ten pin, except nine pins on the first ball of a rack count as a strike
+    pub fn nine_pin_no_tap() -> Self {
+        Rules::ten_pin().with_no_tap(9)
+    }
+
+    /// A game of `frames` frames, the last one taking the fill balls
+    pub fn with_frames(mut self, frames: usize) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Count `pins` or more on the first ball of a rack as a strike
+    pub fn with_no_tap(mut self, pins: u16) -> Self {
+        self.strike_at = pins;
+        self
+    }
+
+    pub fn balls(&self) -> usize {
+        self.balls
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.frames == 0 || self.frames > MAX_FRAMES {
+            return Err(Error::InvalidFrameCount(self.frames));
+        }
+        if self.strike_at == 0 || self.strike_at > PINS {
+            return Err(Error::InvalidNoTapCount(self.strike_at));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! The frame by frame breakdown shown on a live scoreboard.
+
+use crate::BowlingGame;
+use std::fmt;
+
+/// One frame as far as it has been bowled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameScore {
+    /// Pins knocked down by each roll, fill balls included
+    pub rolls: Vec<u16>,
+    /// One mark per roll: `X` for a strike, `/` for a spare, `-` for no pins, or the
+    /// pin count
+    pub marks: String,
+    /// Running total up to and including this frame, once its bonus rolls are in
+    pub score: Option<u16>,
+}
+
+impl BowlingGame {
+    /// Every frame rolled so far, including one still in progress
+    /// O(n) for n frames
+    pub fn frames(&self) -> Vec<FrameScore> {
+        let mut total = Some(0);
+        self.scorecard
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                total = total.and_then(|t| Some(t + self.frame_score(i)?));
+                FrameScore {
+                    rolls: frame.rolls.clone(),
+                    marks: frame.marks(),
+                    score: total,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The traditional ten box scorecard: marks on the top row, running totals underneath
+/// and blank boxes for frames still to come. Each box has room for a mark per ball,
+/// and the last one for its fill balls too.
+///
+/// ```text
+/// +---+---+---+---+---+---+---+---+---+-----+
+/// |  X|7 /|9 -|   |   |   |   |   |   |     |
+/// | 20| 39| 48|   |   |   |   |   |   |     |
+/// +---+---+---+---+---+---+---+---+---+-----+
+/// ```
+impl fmt::Display for BowlingGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let frames = self.frames();
+        let count = self.rules.frames;
+        let balls = self.rules.balls;
+        let width = |i: usize| 2 * if i == count - 1 { balls.max(3) } else { balls } - 1;
+        let border: String = (0..count)
+            .map(|i| format!("+{}", "-".repeat(width(i))))
+            .collect();
+
+        let mut marks = String::new();
+        let mut scores = String::new();
+        for i in 0..count {
+            let frame = frames.get(i);
+            let mut box_marks = frame.map_or(String::new(), |frame| {
+                let mut chars: Vec<String> = frame.marks.chars().map(String::from).collect();
+                if i < count - 1 && frame.marks == "X" {
+                    // a strike goes in the right hand box
+                    chars.splice(0..0, vec![" ".to_string(); balls - 1]);
+                }
+                chars.join(" ")
+            });
+            box_marks = format!("{:<width$}", box_marks, width = width(i));
+            let score = frame
+                .and_then(|frame| frame.score)
+                .map_or(String::new(), |score| score.to_string());
+            marks += &format!("|{}", box_marks);
+            scores += &format!("|{:>width$}", score, width = width(i));
+        }
+
+        writeln!(f, "{}+", border)?;
+        writeln!(f, "{}|", marks)?;
+        writeln!(f, "{}|", scores)?;
+        write!(f, "{}+", border)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(rolls: &[u16]) -> BowlingGame {
+        let mut game = BowlingGame::new();
+        for &pins in rolls {
+            game.roll(pins).unwrap();
+        }
+        game
+    }
+
+    fn test(rolls: &[u16], expected: &[(&str, Option<u16>)]) {
+        let frames = game(rolls).frames();
+        let actual: Vec<_> = frames.iter().map(|f| (f.marks.as_str(), f.score)).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn open_frames_score_straight_away() {
+        test(
+            &[3, 4, 0, 9, 2],
+            &[("34", Some(7)), ("-9", Some(16)), ("2", None)],
+        );
+    }
+
+    #[test]
+    fn bonuses_hold_back_the_running_total() {
+        test(&[10, 7, 3], &[("X", Some(20)), ("7/", None)]);
+        test(
+            &[10, 7, 3, 0],
+            &[("X", Some(20)), ("7/", Some(30)), ("-", None)],
+        );
+        test(&[10, 10, 10], &[("X", Some(30)), ("X", None), ("X", None)]);
+        test(&[0, 10, 5], &[("-/", Some(15)), ("5", None)]);
+    }
+
+    #[test]
+    fn last_frame_marks_fill_balls() {
+        let nine_open = [0; 18];
+        for (fill, marks, score) in [
+            (&[10, 10, 10][..], "XXX", 30),
+            (&[10, 3, 7][..], "X3/", 20),
+            (&[10, 0, 2][..], "X-2", 12),
+            (&[0, 10, 10][..], "-/X", 20),
+            (&[4, 5][..], "45", 9),
+        ]
+        .iter()
+        {
+            let rolls: Vec<u16> = nine_open.iter().chain(fill.iter()).copied().collect();
+            let frames = game(&rolls).frames();
+            let last = frames.last().unwrap();
+            assert_eq!(last.marks, *marks);
+            assert_eq!(last.rolls, *fill);
+            assert_eq!(last.score, Some(*score));
+        }
+    }
+
+    #[test]
+    fn no_rolls_no_frames() {
+        assert!(BowlingGame::new().frames().is_empty());
+    }
+
+    #[test]
+    fn renders_a_game_in_progress() {
+        assert_eq!(
+            game(&[10, 7, 3, 9, 0, 10]).to_string(),
+            "+---+---+---+---+---+---+---+---+---+-----+\n\
+             |  X|7 /|9 -|  X|   |   |   |   |   |     |\n\
+             | 20| 39| 48|   |   |   |   |   |   |     |\n\
+             +---+---+---+---+---+---+---+---+---+-----+"
+        );
+    }
+
+    #[test]
+    fn renders_a_perfect_game() {
+        assert_eq!(
+            game(&[10; 12]).to_string(),
+            "+---+---+---+---+---+---+---+---+---+-----+\n\
+             |  X|  X|  X|  X|  X|  X|  X|  X|  X|X X X|\n\
+             | 30| 60| 90|120|150|180|210|240|270|  300|\n\
+             +---+---+---+---+---+---+---+---+---+-----+"
+        );
+    }
+
+    #[test]
+    fn renders_three_ball_frames() {
+        let mut game = BowlingGame::with_rules(crate::Rules::candlepin().with_frames(3)).unwrap();
+        for &pins in &[10, 3, 7, 1, 0, 9] {
+            game.roll(pins).unwrap();
+        }
+        assert_eq!(
+            game.to_string(),
+            "+-----+-----+-----+\n\
+             |    X|3 /  |1 - /|\n\
+             |   20|   31|   41|\n\
+             +-----+-----+-----+"
+        );
+    }
+}
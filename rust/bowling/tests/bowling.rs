@@ -141,7 +141,6 @@ fn if_the_last_frame_is_a_spare_you_get_one_extra_roll_that_is_scored_once() {
 }
 
 #[test]
-#[ignore]
 fn a_strike_earns_ten_points_in_a_frame_with_a_single_roll() {
     let mut game = BowlingGame::new();
 
@@ -155,7 +154,6 @@ fn a_strike_earns_ten_points_in_a_frame_with_a_single_roll() {
 }
 
 #[test]
-#[ignore]
 fn points_scored_in_the_two_rolls_after_a_strike_are_counted_twice_as_a_bonus() {
     let mut game = BowlingGame::new();
 
@@ -171,7 +169,6 @@ fn points_scored_in_the_two_rolls_after_a_strike_are_counted_twice_as_a_bonus()
 }
 
 #[test]
-#[ignore]
 fn consecutive_strikes_each_get_the_two_roll_bonus() {
     let mut game = BowlingGame::new();
 
@@ -189,7 +186,6 @@ fn consecutive_strikes_each_get_the_two_roll_bonus() {
 }
 
 #[test]
-#[ignore]
 fn a_strike_in_the_last_frame_earns_a_two_roll_bonus_that_is_counted_once() {
     let mut game = BowlingGame::new();
 
@@ -205,7 +201,6 @@ fn a_strike_in_the_last_frame_earns_a_two_roll_bonus_that_is_counted_once() {
 }
 
 #[test]
-#[ignore]
 fn a_spare_with_the_two_roll_bonus_does_not_get_a_bonus_roll() {
     let mut game = BowlingGame::new();
 
@@ -221,7 +216,6 @@ fn a_spare_with_the_two_roll_bonus_does_not_get_a_bonus_roll() {
 }
 
 #[test]
-#[ignore]
 fn strikes_with_the_two_roll_bonus_do_not_get_a_bonus_roll() {
     let mut game = BowlingGame::new();
 
@@ -237,7 +231,6 @@ fn strikes_with_the_two_roll_bonus_do_not_get_a_bonus_roll() {
 }
 
 #[test]
-#[ignore]
 fn a_strike_with_the_one_roll_bonus_after_a_spare_in_the_last_frame_does_not_get_a_bonus() {
     let mut game = BowlingGame::new();
 
@@ -253,7 +246,6 @@ fn a_strike_with_the_one_roll_bonus_after_a_spare_in_the_last_frame_does_not_get
 }
 
 #[test]
-#[ignore]
 fn all_strikes_is_a_perfect_score_of_300() {
     let mut game = BowlingGame::new();
 
@@ -265,7 +257,6 @@ fn all_strikes_is_a_perfect_score_of_300() {
 }
 
 #[test]
-#[ignore]
 fn you_cannot_roll_more_than_ten_pins_in_a_single_frame() {
     let mut game = BowlingGame::new();
 
@@ -274,7 +265,6 @@ fn you_cannot_roll_more_than_ten_pins_in_a_single_frame() {
 }
 
 #[test]
-#[ignore]
 fn first_bonus_ball_after_a_final_strike_cannot_score_an_invalid_number_of_pins() {
     let mut game = BowlingGame::new();
 
@@ -288,7 +278,6 @@ fn first_bonus_ball_after_a_final_strike_cannot_score_an_invalid_number_of_pins(
 }
 
 #[test]
-#[ignore]
 fn the_two_balls_after_a_final_strike_cannot_score_an_invalid_number_of_pins() {
     let mut game = BowlingGame::new();
 
@@ -303,7 +292,6 @@ fn the_two_balls_after_a_final_strike_cannot_score_an_invalid_number_of_pins() {
 }
 
 #[test]
-#[ignore]
 fn the_two_balls_after_a_final_strike_can_be_a_strike_and_non_strike() {
     let mut game = BowlingGame::new();
 
@@ -318,7 +306,6 @@ fn the_two_balls_after_a_final_strike_can_be_a_strike_and_non_strike() {
 }
 
 #[test]
-#[ignore]
 fn the_two_balls_after_a_final_strike_cannot_be_a_non_strike_followed_by_a_strike() {
     let mut game = BowlingGame::new();
 
@@ -333,7 +320,6 @@ fn the_two_balls_after_a_final_strike_cannot_be_a_non_strike_followed_by_a_strik
 }
 
 #[test]
-#[ignore]
 fn second_bonus_ball_after_a_final_strike_cannot_score_an_invalid_number_of_pins_even_if_first_is_strike(
 ) {
     let mut game = BowlingGame::new();
@@ -349,7 +335,6 @@ fn second_bonus_ball_after_a_final_strike_cannot_score_an_invalid_number_of_pins
 }
 
 #[test]
-#[ignore]
 fn if_the_last_frame_is_a_strike_you_cannot_score_before_the_extra_rolls_are_taken() {
     let mut game = BowlingGame::new();
 
@@ -371,7 +356,6 @@ fn if_the_last_frame_is_a_strike_you_cannot_score_before_the_extra_rolls_are_tak
 }
 
 #[test]
-#[ignore]
 fn if_the_last_frame_is_a_spare_you_cannot_create_a_score_before_extra_roll_is_taken() {
     let mut game = BowlingGame::new();
 
@@ -390,7 +374,6 @@ fn if_the_last_frame_is_a_spare_you_cannot_create_a_score_before_extra_roll_is_t
 }
 
 #[test]
-#[ignore]
 fn cannot_roll_after_bonus_roll_for_spare() {
     let mut game = BowlingGame::new();
 
@@ -406,7 +389,6 @@ fn cannot_roll_after_bonus_roll_for_spare() {
 }
 
 #[test]
-#[ignore]
 fn cannot_roll_after_bonus_roll_for_strike() {
     let mut game = BowlingGame::new();
 
@@ -422,7 +404,6 @@ fn cannot_roll_after_bonus_roll_for_strike() {
 }
 
 #[test]
-#[ignore]
 fn last_two_strikes_followed_by_only_last_bonus_with_non_strike_points() {
     let mut game = BowlingGame::new();
     for _ in 0..16 {
@@ -435,3 +416,234 @@ fn last_two_strikes_followed_by_only_last_bonus_with_non_strike_points() {
 
     assert_eq!(game.score(), Some(31));
 }
+
+#[test]
+fn the_fill_ball_after_a_final_spare_has_a_full_rack() {
+    let mut game = BowlingGame::new();
+    for _ in 0..18 {
+        let _ = game.roll(0);
+    }
+    let _ = game.roll(3);
+    let _ = game.roll(7);
+
+    assert_eq!(game.roll(11), Err(Error::NotEnoughPinsLeft));
+    assert!(game.roll(10).is_ok());
+    assert_eq!(game.score(), Some(20));
+}
+
+#[test]
+fn an_open_last_frame_gets_no_fill_ball() {
+    let mut game = BowlingGame::new();
+    for _ in 0..18 {
+        let _ = game.roll(5);
+    }
+    let _ = game.roll(3);
+    let _ = game.roll(6);
+
+    assert!(game.complete());
+    assert_eq!(game.roll(1), Err(Error::GameComplete));
+    assert_eq!(game.score(), Some(8 * 15 + 13 + 9));
+}
+
+#[test]
+fn frames_show_rolls_marks_and_running_totals() {
+    let mut game = BowlingGame::new();
+    for &pins in &[10, 6, 4, 0, 3, 2] {
+        let _ = game.roll(pins);
+    }
+
+    assert_eq!(
+        game.frames(),
+        vec![
+            FrameScore {
+                rolls: vec![10],
+                marks: "X".to_string(),
+                score: Some(20),
+            },
+            FrameScore {
+                rolls: vec![6, 4],
+                marks: "6/".to_string(),
+                score: Some(30),
+            },
+            FrameScore {
+                rolls: vec![0, 3],
+                marks: "-3".to_string(),
+                score: Some(33),
+            },
+            FrameScore {
+                rolls: vec![2],
+                marks: "2".to_string(),
+                score: None,
+            },
+        ]
+    );
+}
+
+fn roll_all(game: &mut BowlingGame, rolls: &[u16]) {
+    for &pins in rolls {
+        game.roll(pins).unwrap();
+    }
+}
+
+#[test]
+fn undo_takes_back_the_last_roll() {
+    let mut game = BowlingGame::new();
+    assert_eq!(game.undo(), None);
+    roll_all(&mut game, &[10; 12]);
+    assert!(game.complete());
+
+    assert_eq!(game.undo(), Some(10));
+    assert!(!game.complete());
+    assert!(game.roll(4).is_ok());
+    assert_eq!(game.score(), Some(294));
+    assert_eq!(game.frames().len(), 10);
+}
+
+#[test]
+fn undoing_the_first_roll_of_a_frame_removes_the_frame() {
+    let mut game = BowlingGame::new();
+    roll_all(&mut game, &[3, 4, 5]);
+    assert_eq!(game.undo(), Some(5));
+    assert_eq!(game.frames().len(), 1);
+    assert_eq!(game.undo(), Some(4));
+    assert_eq!(game.undo(), Some(3));
+    assert!(game.frames().is_empty());
+}
+
+#[test]
+fn candlepin_frames_have_three_balls() {
+    let mut game = BowlingGame::with_rules(Rules::candlepin()).unwrap();
+    roll_all(&mut game, &[3; 29]);
+    assert!(!game.complete());
+    game.roll(3).unwrap();
+    assert_eq!(game.roll(3), Err(Error::GameComplete));
+    assert_eq!(game.score(), Some(90));
+}
+
+#[test]
+fn candlepin_pins_add_up_over_three_balls() {
+    let mut game = BowlingGame::with_rules(Rules::duckpin()).unwrap();
+    roll_all(&mut game, &[4, 4]);
+    assert_eq!(game.roll(3), Err(Error::NotEnoughPinsLeft));
+}
+
+#[test]
+fn candlepin_third_ball_clearing_the_rack_gets_no_bonus() {
+    let mut game = BowlingGame::with_rules(Rules::candlepin()).unwrap();
+    // a spare on the second ball, then a ten-box on the third
+    roll_all(&mut game, &[5, 5, 3, 3, 4, 6, 0, 0]);
+    roll_all(&mut game, &[0; 21]);
+    assert_eq!(game.score(), Some(13 + 10 + 6));
+}
+
+#[test]
+fn candlepin_perfect_game() {
+    let mut game = BowlingGame::with_rules(Rules::candlepin()).unwrap();
+    roll_all(&mut game, &[10; 12]);
+    assert_eq!(game.score(), Some(300));
+}
+
+#[test]
+fn nine_pins_on_the_first_ball_are_a_strike_in_no_tap() {
+    let mut game = BowlingGame::with_rules(Rules::nine_pin_no_tap()).unwrap();
+    roll_all(&mut game, &[9; 12]);
+    assert!(game.complete());
+    assert_eq!(game.score(), Some(300));
+}
+
+#[test]
+fn no_tap_only_applies_to_a_full_rack() {
+    let mut game = BowlingGame::with_rules(Rules::nine_pin_no_tap()).unwrap();
+    roll_all(&mut game, &[0, 9, 1, 9]);
+    roll_all(&mut game, &[0; 16]);
+    assert_eq!(game.score(), Some(9 + 10));
+    assert_eq!(game.frames()[1].marks, "1/");
+}
+
+#[test]
+fn games_can_be_shortened() {
+    let mut game = BowlingGame::with_rules(Rules::ten_pin().with_frames(3)).unwrap();
+    roll_all(&mut game, &[10; 5]);
+    assert!(game.complete());
+    assert_eq!(game.score(), Some(90));
+    assert_eq!(game.roll(0), Err(Error::GameComplete));
+}
+
+#[test]
+fn the_longest_perfect_game_still_has_a_score() {
+    let mut game = BowlingGame::with_rules(Rules::ten_pin().with_frames(MAX_FRAMES)).unwrap();
+    roll_all(&mut game, &vec![10; MAX_FRAMES + 2]);
+    assert!(game.complete());
+    assert_eq!(game.score(), Some(30 * MAX_FRAMES as u16));
+    let frames = game.frames();
+    assert_eq!(frames.last().unwrap().score, game.score());
+}
+
+#[test]
+fn rule_variants_are_validated() {
+    assert_eq!(
+        BowlingGame::with_rules(Rules::ten_pin().with_frames(0)).err(),
+        Some(Error::InvalidFrameCount(0))
+    );
+    assert_eq!(
+        BowlingGame::with_rules(Rules::ten_pin().with_frames(MAX_FRAMES + 1)).err(),
+        Some(Error::InvalidFrameCount(MAX_FRAMES + 1))
+    );
+    assert_eq!(
+        BowlingGame::with_rules(Rules::ten_pin().with_no_tap(0)).err(),
+        Some(Error::InvalidNoTapCount(0))
+    );
+    assert_eq!(
+        BowlingGame::with_rules(Rules::candlepin().with_no_tap(11)).err(),
+        Some(Error::InvalidNoTapCount(11))
+    );
+}
+
+#[test]
+fn players_take_turns_a_frame_at_a_time() {
+    let mut game = Match::new(&["ann", "bob"]).unwrap();
+    assert_eq!(game.current_player(), Some("ann"));
+    game.roll(10).unwrap();
+    assert_eq!(game.current_player(), Some("bob"));
+    game.roll(3).unwrap();
+    assert_eq!(game.current_player(), Some("bob"));
+    game.roll(4).unwrap();
+    assert_eq!(game.current_player(), Some("ann"));
+
+    assert_eq!(game.undo(), Some(4));
+    assert_eq!(game.current_player(), Some("bob"));
+    game.roll(7).unwrap();
+
+    for _ in 0..9 * 2 * 2 {
+        game.roll(0).unwrap();
+    }
+    assert!(game.complete());
+    assert_eq!(game.current_player(), None);
+    assert_eq!(game.roll(0), Err(Error::GameComplete));
+    assert_eq!(game.scores(), vec![("ann", Some(10)), ("bob", Some(10))]);
+    assert_eq!(game.game("ann").unwrap().frames()[0].marks, "X");
+}
+
+#[test]
+fn fill_balls_are_bowled_in_the_same_turn() {
+    let rules = Rules::ten_pin().with_frames(1);
+    let mut game = Match::with_rules(&["ann", "bob"], rules).unwrap();
+    game.roll(10).unwrap();
+    assert_eq!(game.current_player(), Some("ann"));
+    game.roll(10).unwrap();
+    game.roll(10).unwrap();
+    assert_eq!(game.current_player(), Some("bob"));
+}
+
+#[test]
+fn a_match_needs_different_players() {
+    assert_eq!(Match::new(&[]).err(), Some(Error::NoPlayers));
+    assert_eq!(
+        Match::new(&["ann", "bob", "ann"]).err(),
+        Some(Error::DuplicatePlayer("ann".to_string()))
+    );
+    assert_eq!(
+        Match::with_rules(&["ann"], Rules::ten_pin().with_frames(0)).err(),
+        Some(Error::InvalidFrameCount(0))
+    );
+}
@@ -1,7 +1,8 @@
 #![feature(test)]
 extern crate test;
 
-use custom_set::{FastSet, NaiveSet};
+use custom_set::{FastSet, NaiveSet, OrderedSet};
+use std::collections::HashSet;
 use test::Bencher;
 
 #[bench]
@@ -13,95 +14,160 @@ fn naive_union(b: &mut Bencher) {
 }
 
 #[bench]
-fn fast13_union(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 13>;
+fn fast_union(b: &mut Bencher) {
+    type Set<T> = FastSet<T>;
     let s = Set::new(&[1, 2, 3, 4, 5]);
     let s2 = Set::new(&[3, 4, 5, 6]);
     b.iter(|| s.union(&s2))
 }
 
 #[bench]
-fn fast31_union(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 13>;
-    let s = Set::new(&[1, 2, 3, 4, 5]);
-    let s2 = Set::new(&[3, 4, 5, 6]);
+fn std_union(b: &mut Bencher) {
+    let s: HashSet<i32> = [1, 2, 3, 4, 5].iter().copied().collect();
+    let s2: HashSet<i32> = [3, 4, 5, 6].iter().copied().collect();
+    b.iter(|| &s | &s2)
+}
+
+#[bench]
+fn naive_large_union(b: &mut Bencher) {
+    let s: NaiveSet<i32> = (0..1000).collect();
+    let s2: NaiveSet<i32> = (500..1500).collect();
+    b.iter(|| s.union(&s2))
+}
+
+#[bench]
+fn fast_large_union(b: &mut Bencher) {
+    let s: FastSet<i32> = (0..1000).collect();
+    let s2: FastSet<i32> = (500..1500).collect();
     b.iter(|| s.union(&s2))
 }
 
+#[bench]
+fn std_large_union(b: &mut Bencher) {
+    let s: HashSet<i32> = (0..1000).collect();
+    let s2: HashSet<i32> = (500..1500).collect();
+    b.iter(|| &s | &s2)
+}
+
 #[bench]
 fn naive_add(b: &mut Bencher) {
-    type Set<T> = NaiveSet<T>;
-    let mut s = Set::new(&[]);
     b.iter(|| {
+        let mut s = NaiveSet::new(&[]);
         for i in 1..10000 {
             s.add(i);
         }
+        s
     })
 }
 
 #[bench]
-fn fast13_add(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 13>;
-    let mut s = Set::new(&[]);
+fn fast_add(b: &mut Bencher) {
     b.iter(|| {
+        let mut s = FastSet::new(&[]);
         for i in 1..10000 {
             s.add(i);
         }
+        s
     })
 }
 
 #[bench]
-fn fast31_add(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 31>;
-    let mut s = Set::new(&[]);
+fn std_add(b: &mut Bencher) {
     b.iter(|| {
+        let mut s = HashSet::new();
         for i in 1..10000 {
-            s.add(i);
+            s.insert(i);
         }
+        s
     })
 }
 
 #[bench]
-fn fast13_contains(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 13>;
-    let mut s = Set::new(&[]);
-    for i in 1..1000 {
-        s.add(i);
-    }
+fn naive_contains(b: &mut Bencher) {
+    let s: NaiveSet<i32> = (1..1000).collect();
+    b.iter(|| (500..1500).filter(|i| s.contains(i)).count())
+}
+
+#[bench]
+fn fast_contains(b: &mut Bencher) {
+    let s: FastSet<i32> = (1..1000).collect();
+    b.iter(|| (500..1500).filter(|i| s.contains(i)).count())
+}
+
+#[bench]
+fn std_contains(b: &mut Bencher) {
+    let s: HashSet<i32> = (1..1000).collect();
+    b.iter(|| (500..1500).filter(|i| s.contains(i)).count())
+}
+
+#[bench]
+fn fast_large_contains(b: &mut Bencher) {
+    let s: FastSet<i32> = (1..100_000).collect();
+    b.iter(|| (50_000..150_000).filter(|i| s.contains(i)).count())
+}
+
+#[bench]
+fn std_large_contains(b: &mut Bencher) {
+    let s: HashSet<i32> = (1..100_000).collect();
+    b.iter(|| (50_000..150_000).filter(|i| s.contains(i)).count())
+}
 
+#[bench]
+fn fast_add_remove(b: &mut Bencher) {
+    let mut s: FastSet<i32> = (0..1000).collect();
     b.iter(|| {
-        for i in 500..1500 {
-            s.contains(&i);
+        for i in 0..1000 {
+            s.remove(&i);
+            s.add(i + 1000);
+        }
+        for i in 0..1000 {
+            s.remove(&(i + 1000));
+            s.add(i);
         }
     })
 }
 
 #[bench]
-fn fast31_contains(b: &mut Bencher) {
-    type Set<T> = FastSet<T, 31>;
-    let mut s = Set::new(&[]);
-    for i in 1..1000 {
-        s.add(i);
-    }
-
+fn std_add_remove(b: &mut Bencher) {
+    let mut s: HashSet<i32> = (0..1000).collect();
     b.iter(|| {
-        for i in 500..1500 {
-            s.contains(&i);
+        for i in 0..1000 {
+            s.remove(&i);
+            s.insert(i + 1000);
+        }
+        for i in 0..1000 {
+            s.remove(&(i + 1000));
+            s.insert(i);
         }
     })
 }
 
 #[bench]
-fn naive_contains(b: &mut Bencher) {
-    type Set<T> = NaiveSet<T>;
-    let mut s = Set::new(&[]);
-    for i in 1..1000 {
-        s.add(i);
-    }
+fn ordered_large_union(b: &mut Bencher) {
+    let s: OrderedSet<i32> = (0..1000).collect();
+    let s2: OrderedSet<i32> = (500..1500).collect();
+    b.iter(|| s.union(&s2))
+}
 
+#[bench]
+fn ordered_add(b: &mut Bencher) {
     b.iter(|| {
-        for i in 500..1500 {
-            s.contains(&i);
+        let mut s = OrderedSet::new(&[]);
+        for i in 1..10000 {
+            s.add(i);
         }
+        s
     })
 }
+
+#[bench]
+fn ordered_contains(b: &mut Bencher) {
+    let s: OrderedSet<i32> = (1..1000).collect();
+    b.iter(|| (500..1500).filter(|i| s.contains(i)).count())
+}
+
+#[bench]
+fn ordered_range(b: &mut Bencher) {
+    let s: OrderedSet<i32> = (1..100_000).collect();
+    b.iter(|| s.range(50_000..50_100).count())
+}
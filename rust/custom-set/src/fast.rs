@@ -1,117 +1,576 @@
-use std::hash::{Hash, Hasher};
+use crate::Set;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use std::collections::hash_map::DefaultHasher;
+/// Grow once more than 7 in 8 slots would be full
+const MAX_LOAD: (usize, usize) = (7, 8);
+const MIN_CAPACITY: usize = 8;
 
-/// The Fast implementation using hashing
-#[derive(Debug)]
-pub struct FastSet<T, const N: usize> {
-    v: Vec<Vec<T>>,
+#[derive(Clone)]
+struct Slot<T> {
+    hash: u64,
+    e: T,
 }
 
-fn get_hashed_bucket<T: Hash>(e: &T, n: usize) -> usize {
-    let mut s = DefaultHasher::new();
-    e.hash(&mut s);
-    s.finish() as usize % n
+/// The Fast implementation using hashing.
+///
+/// An open addressing table with Robin Hood probing: an element being inserted takes
+/// the slot of any element sitting closer to its own ideal slot, which keeps probe
+/// sequences short and lets a lookup stop as soon as it passes where the element would
+/// have been. The table doubles when it gets too full, so lookups stay O(1) on average
+/// however big the set grows.
+#[derive(Clone)]
+pub struct FastSet<T, S = RandomState> {
+    /// Always empty or a power of two long
+    slots: Vec<Option<Slot<T>>>,
+    len: usize,
+    hasher: S,
 }
 
-impl<T, const N: usize> FastSet<T, N>
+impl<T, S: Default> Default for FastSet<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T: Clone + PartialEq + Hash> FastSet<T, RandomState> {
+    pub fn new(input: &[T]) -> Self {
+        input.iter().cloned().collect()
+    }
+
+    /// An empty set with room for `capacity` elements before it has to grow
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<T, S> FastSet<T, S> {
+    /// An empty set hashing its elements with `hasher`. Doesn't allocate.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+            hasher,
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut new = Self::with_hasher(hasher);
+        if capacity > 0 {
+            new.slots = empty_slots(slots_for(capacity));
+        }
+        new
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many elements fit before the table has to grow
+    pub fn capacity(&self) -> usize {
+        self.slots.len() * MAX_LOAD.0 / MAX_LOAD.1
+    }
+
+    /// Empties the set, keeping its memory
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.slots.iter(),
+        }
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    /// How far the element hashed to `hash` sits past its ideal slot if it's in `i`
+    fn distance(&self, hash: u64, i: usize) -> usize {
+        i.wrapping_sub(hash as usize) & self.mask()
+    }
+
+    /// Puts an element known not to be in the set into a table with room for it
+    fn place(&mut self, mut carry: Slot<T>) {
+        let mask = self.mask();
+        let mut i = carry.hash as usize & mask;
+        let mut dist = 0;
+        loop {
+            let resident = match &self.slots[i] {
+                None => {
+                    self.slots[i] = Some(carry);
+                    return;
+                }
+                Some(resident) => self.distance(resident.hash, i),
+            };
+            if resident < dist {
+                // the resident is better off than us, so it moves on instead
+                std::mem::swap(self.slots[i].as_mut().unwrap(), &mut carry);
+                dist = resident;
+            }
+            i = (i + 1) & mask;
+            dist += 1;
+        }
+    }
+
+    /// Doubles the table, moving every element using the hash it was stored with
+    /// O(n)
+    fn grow(&mut self) {
+        let capacity = (self.slots.len() * 2).max(MIN_CAPACITY);
+        let old = std::mem::replace(&mut self.slots, empty_slots(capacity));
+        for slot in old.into_iter().flatten() {
+            self.place(slot);
+        }
+    }
+}
+
+impl<T, S> FastSet<T, S>
 where
     T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
 {
-    pub fn new(input: &[T]) -> Self {
-        let mut new = Self {
-            v: vec![Vec::with_capacity(4); N],
-        };
+    fn hash(&self, e: &T) -> u64 {
+        self.hasher.hash_one(e)
+    }
 
-        input.iter().cloned().for_each(|e| {
-            new.add(e);
-        });
+    fn find(&self, element: &T) -> Option<usize> {
+        self.find_hashed(element, self.hash(element))
+    }
 
-        new
+    /// The slot holding `element`, which hashes to `hash`. Stops at the first slot whose
+    /// element is closer to its ideal slot than `element` would be, since Robin Hood
+    /// would have put `element` there.
+    fn find_hashed(&self, element: &T, hash: u64) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let mask = self.mask();
+        let mut i = hash as usize & mask;
+        let mut dist = 0;
+        while let Some(slot) = &self.slots[i] {
+            if self.distance(slot.hash, i) < dist {
+                return None;
+            }
+            if slot.hash == hash && slot.e == *element {
+                return Some(i);
+            }
+            i = (i + 1) & mask;
+            dist += 1;
+        }
+        None
     }
 
+    /// O(1) on average
     pub fn contains(&self, element: &T) -> bool {
-        let b = get_hashed_bucket(element, N);
-        self.v[b].contains(element)
+        self.find(element).is_some()
     }
 
-    pub fn add(&mut self, e: T) {
-        let bucket = get_hashed_bucket(&e, N);
-        if !self.v[bucket].contains(&e) {
-            self.v[bucket].push(e);
+    /// True if the element wasn't already there.
+    /// O(1) on average
+    pub fn add(&mut self, e: T) -> bool {
+        let hash = self.hash(&e);
+        if self.find_hashed(&e, hash).is_some() {
+            return false;
         }
+        if (self.len + 1) * MAX_LOAD.1 > self.slots.len() * MAX_LOAD.0 {
+            self.grow();
+        }
+        self.place(Slot { hash, e });
+        self.len += 1;
+        true
     }
 
-    pub fn is_subset(&self, other: &Self) -> bool {
-        self.iter().all(|e| other.iter().any(|e2| e == e2))
+    /// True if the element was there. The elements after it in its run shift back a
+    /// slot, so no tombstones are left behind.
+    /// O(1) on average
+    pub fn remove(&mut self, element: &T) -> bool {
+        let Some(mut i) = self.find(element) else {
+            return false;
+        };
+        let mask = self.mask();
+        self.slots[i] = None;
+        loop {
+            let next = (i + 1) & mask;
+            match &self.slots[next] {
+                Some(slot) if self.distance(slot.hash, next) > 0 => {
+                    self.slots[i] = self.slots[next].take();
+                    i = next;
+                }
+                _ => break,
+            }
+        }
+        self.len -= 1;
+        true
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.v.iter().all(|b| b.is_empty())
+    /// O(n) on average
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len <= other.len && self.iter().all(|e| other.contains(e))
     }
 
+    /// O(min(n, m)) on average
     pub fn is_disjoint(&self, other: &Self) -> bool {
-        self.iter().all(|e| !other.iter().any(|e2| e == e2))
+        let (small, large) = if self.len <= other.len {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        small.iter().all(|e| !large.contains(e))
+    }
+
+    /// An empty set using the same hasher
+    fn empty(&self) -> Self {
+        Self::with_hasher(self.hasher.clone())
     }
 
     #[must_use]
     pub fn intersection(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
-        self.iter()
-            .chain(other.iter())
-            .cloned()
-            .filter(|e| self.contains(e) && other.contains(e))
-            .for_each(|e| new.add(e));
+        let mut new = self.empty();
+        new.extend(self.iter().filter(|e| other.contains(e)).cloned());
         new
     }
 
     #[must_use]
     pub fn difference(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
-        self.iter()
-            .filter(|e| !other.contains(e))
-            .cloned()
-            .for_each(|e| new.add(e));
+        let mut new = self.empty();
+        new.extend(self.iter().filter(|e| !other.contains(e)).cloned());
+        new
+    }
+
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut new = self.difference(other);
+        new.extend(other.iter().filter(|e| !self.contains(e)).cloned());
         new
     }
 
     #[must_use]
     pub fn union(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
-        self.iter()
-            .chain(other.iter())
-            .cloned()
-            .for_each(|e| new.add(e));
+        let mut new = self.clone();
+        new.extend(other.iter().cloned());
         new
     }
+}
+
+/// Each method forwards to the inherent method of the same name
+impl<T, S> Set<T> for FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone + Default,
+{
+    fn new(input: &[T]) -> Self {
+        input.iter().cloned().collect()
+    }
+
+    fn contains(&self, element: &T) -> bool {
+        FastSet::contains(self, element)
+    }
 
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.v.iter().flatten()
+    fn add(&mut self, element: T) -> bool {
+        FastSet::add(self, element)
+    }
+
+    fn remove(&mut self, element: &T) -> bool {
+        FastSet::remove(self, element)
+    }
+
+    fn len(&self) -> usize {
+        FastSet::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        FastSet::is_empty(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        FastSet::iter(self)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        FastSet::is_subset(self, other)
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        FastSet::is_disjoint(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        FastSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        FastSet::difference(self, other)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        FastSet::symmetric_difference(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        FastSet::union(self, other)
     }
 }
 
-impl<T: Clone + PartialEq + Hash, const N: usize> PartialEq for FastSet<T, N> {
+/// The smallest power of two number of slots holding `capacity` elements
+fn slots_for(capacity: usize) -> usize {
+    (capacity * MAX_LOAD.1)
+        .div_ceil(MAX_LOAD.0)
+        .next_power_of_two()
+        .max(MIN_CAPACITY)
+}
+
+fn empty_slots<T>(n: usize) -> Vec<Option<Slot<T>>> {
+    std::iter::repeat_with(|| None).take(n).collect()
+}
+
+/// Borrows the elements of a `FastSet` in no particular order
+pub struct Iter<'a, T> {
+    slots: std::slice::Iter<'a, Option<Slot<T>>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.slots.by_ref().flatten().next().map(|slot| &slot.e)
+    }
+}
+
+/// Takes the elements out of a `FastSet` in no particular order
+pub struct IntoIter<T> {
+    slots: std::vec::IntoIter<Option<Slot<T>>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.slots.by_ref().flatten().next().map(|slot| slot.e)
+    }
+}
+
+impl<T, S> PartialEq for FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
     fn eq(&self, rhs: &Self) -> bool {
-        self.is_subset(rhs) && rhs.is_subset(self)
+        self.len() == rhs.len() && self.is_subset(rhs)
+    }
+}
+
+impl<T: Clone + Eq + Hash, S: BuildHasher + Clone> Eq for FastSet<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for FastSet<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> FromIterator<T> for FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut new = Self::default();
+        new.extend(iter);
+        new
+    }
+}
+
+impl<T, S> Extend<T> for FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for e in iter {
+            self.add(e);
+        }
+    }
+}
+
+impl<T, S> IntoIterator for FastSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            slots: self.slots.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a FastSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T, S> BitOr<&FastSet<T, S>> for &FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Output = FastSet<T, S>;
+
+    fn bitor(self, rhs: &FastSet<T, S>) -> FastSet<T, S> {
+        self.union(rhs)
+    }
+}
+
+impl<T, S> BitAnd<&FastSet<T, S>> for &FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Output = FastSet<T, S>;
+
+    fn bitand(self, rhs: &FastSet<T, S>) -> FastSet<T, S> {
+        self.intersection(rhs)
+    }
+}
+
+impl<T, S> Sub<&FastSet<T, S>> for &FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Output = FastSet<T, S>;
+
+    fn sub(self, rhs: &FastSet<T, S>) -> FastSet<T, S> {
+        self.difference(rhs)
+    }
+}
+
+impl<T, S> BitXor<&FastSet<T, S>> for &FastSet<T, S>
+where
+    T: Clone + PartialEq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Output = FastSet<T, S>;
+
+    fn bitxor(self, rhs: &FastSet<T, S>) -> FastSet<T, S> {
+        self.symmetric_difference(rhs)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// Sends everything to the same slot, so every lookup has to probe
+    #[derive(Default)]
+    struct Collide;
+
+    impl Hasher for Collide {
+        fn finish(&self) -> u64 {
+            0
+        }
+
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    type Colliding<T> = FastSet<T, BuildHasherDefault<Collide>>;
 
     #[test]
     fn two_hashes_are_always_the_same() {
-        let x = 8i32;
-        let y = 8i32;
-        let mut hasher = DefaultHasher::new();
-        x.hash(&mut hasher);
-        let x_h = hasher.finish();
+        let hasher = BuildHasherDefault::<DefaultHasher>::default();
+        assert_eq!(hasher.hash_one(8i32), hasher.hash_one(8i32));
+    }
 
-        let mut hasher = DefaultHasher::new();
-        y.hash(&mut hasher);
-        let y_h = hasher.finish();
+    #[test]
+    fn len_follows_adds_and_removes() {
+        let mut set = FastSet::new(&[1, 2, 3, 4, 4]);
+        assert_eq!(set.len(), 4);
+        assert!(set.remove(&4));
+        assert!(!set.remove(&4));
+        assert_eq!(set.len(), 3);
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.iter().count(), 0);
+    }
 
-        assert_eq!(x_h, y_h);
+    #[test]
+    fn grows_to_keep_the_load_down() {
+        let mut set = FastSet::new(&[]);
+        assert_eq!(set.capacity(), 0);
+        for i in 0..1000 {
+            set.add(i);
+            assert!(set.len() <= set.capacity());
+        }
+        assert!((0..1000).all(|i| set.contains(&i)));
+        assert!(!set.contains(&1000));
+        assert!(FastSet::<i32>::with_capacity(100).capacity() >= 100);
+    }
+
+    #[test]
+    fn collisions_are_probed_past() {
+        let mut set = Colliding::default();
+        set.extend(0..20);
+        assert!((0..20).all(|i| set.contains(&i)));
+        assert!(!set.contains(&20));
+
+        // removing from the middle of the run shifts the rest back
+        assert!(set.remove(&5));
+        assert!(!set.contains(&5));
+        assert!((0..20).filter(|&i| i != 5).all(|i| set.contains(&i)));
+        assert_eq!(set.len(), 19);
+    }
+
+    #[test]
+    fn matches_std_through_adds_and_removes() {
+        let mut ours = FastSet::new(&[]);
+        let mut std = HashSet::new();
+        // a small linear congruential generator, so the run is repeatable
+        let mut x: u32 = 12345;
+        for _ in 0..5000 {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let e = (x >> 16) % 300;
+            if x >> 31 == 0 {
+                assert_eq!(ours.add(e), std.insert(e));
+            } else {
+                assert_eq!(ours.remove(&e), std.remove(&e));
+            }
+            assert_eq!(ours.len(), std.len());
+        }
+        assert!((0..300).all(|e| ours.contains(&e) == std.contains(&e)));
+        let mut elements: Vec<_> = ours.into_iter().collect();
+        elements.sort_unstable();
+        let mut expected: Vec<_> = std.into_iter().collect();
+        expected.sort_unstable();
+        assert_eq!(elements, expected);
+    }
+
+    #[test]
+    fn set_operations_keep_the_hasher() {
+        let a: Colliding<i32> = (0..5).collect();
+        let b: Colliding<i32> = (3..8).collect();
+        assert_eq!(&a | &b, (0..8).collect());
+        assert_eq!(&a & &b, (3..5).collect());
+        assert!(a.is_disjoint(&(5..10).collect()));
+        assert!((&a & &b).is_subset(&a));
     }
 }
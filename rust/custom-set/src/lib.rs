@@ -1,8 +1,13 @@
 mod fast;
 mod naive;
+mod ordered;
+mod set;
 
 pub use fast::FastSet;
 pub use naive::NaiveSet;
-/// I may implement this in multiple ways. This is the one the tests will use.
-//pub type CustomSet<T> = NaiveSet<T>;
-pub type CustomSet<T> = FastSet<T, 13>;
+pub use ordered::OrderedSet;
+pub use set::Set;
+
+/// The implementation the exercise tests use. Every implementation of [`Set`] is also
+/// checked by the conformance tests.
+pub type CustomSet<T> = FastSet<T>;
@@ -1,15 +1,23 @@
-#![allow(unused)]
+use crate::Set;
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
 /// The Easy Naive Implementation
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct NaiveSet<T> {
     v: Vec<T>,
 }
 
+impl<T> Default for NaiveSet<T> {
+    fn default() -> Self {
+        Self { v: Vec::new() }
+    }
+}
+
 impl<T: Clone + PartialEq> NaiveSet<T> {
-    /// Naive Imple
+    /// Naive Impl: O(n^2)
     pub fn new(input: &[T]) -> Self {
-        Self { v: input.to_vec() }
+        input.iter().cloned().collect()
     }
 
     /// Naive Impl: O(n)
@@ -17,16 +25,29 @@ impl<T: Clone + PartialEq> NaiveSet<T> {
         self.v.contains(element)
     }
 
-    /// Naive Impl: O(n)
-    pub fn add(&mut self, e: T) {
-        if !self.contains(&e) {
-            self.v.push(e);
+    /// Naive Impl: O(n). True if the element wasn't already there.
+    pub fn add(&mut self, e: T) -> bool {
+        if self.contains(&e) {
+            return false;
         }
+        self.v.push(e);
+        true
     }
 
-    /// Naive Impl: O(n^2)
-    pub fn is_subset(&self, other: &Self) -> bool {
-        self.v.iter().all(|e| other.contains(e))
+    /// Naive Impl: O(n). True if the element was there.
+    pub fn remove(&mut self, element: &T) -> bool {
+        match self.v.iter().position(|e| e == element) {
+            Some(i) => {
+                self.v.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Naive Impl: O(1)
+    pub fn len(&self) -> usize {
+        self.v.len()
     }
 
     /// Naive Impl: O(1)
@@ -34,6 +55,15 @@ impl<T: Clone + PartialEq> NaiveSet<T> {
         self.v.is_empty()
     }
 
+    pub fn clear(&mut self) {
+        self.v.clear();
+    }
+
+    /// Naive Impl: O(n^2)
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.v.iter().all(|e| other.contains(e))
+    }
+
     /// Naive Impl: O(n^2)
     pub fn is_disjoint(&self, other: &Self) -> bool {
         self.v.iter().all(|e| !other.contains(e))
@@ -42,46 +72,178 @@ impl<T: Clone + PartialEq> NaiveSet<T> {
     /// Naive Impl: O(n^2)
     #[must_use]
     pub fn intersection(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
         self.v
             .iter()
-            .chain(other.v.iter())
-            .filter(|e| self.contains(e) && other.contains(e))
+            .filter(|e| other.contains(e))
             .cloned()
-            .for_each(|e| new.add(e));
-
-        new
+            .collect()
     }
 
     /// Naive Impl: O(n^2)
     #[must_use]
     pub fn difference(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
         self.v
             .iter()
             .filter(|e| !other.contains(e))
             .cloned()
-            .for_each(|e| new.add(e));
+            .collect()
+    }
 
+    /// Naive Impl: O(n^2)
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut new = self.difference(other);
+        new.extend(other.v.iter().filter(|e| !self.contains(e)).cloned());
         new
     }
 
     #[must_use]
     /// Naive Impl: O(n^2)
     pub fn union(&self, other: &Self) -> Self {
-        let mut new = Self::new(&[]);
-        self.v
-            .iter()
-            .chain(other.v.iter())
-            .cloned()
-            .for_each(|e| new.add(e));
-
+        let mut new = self.clone();
+        new.extend(other.v.iter().cloned());
         new
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.v.iter()
+    }
+}
+
+/// Each method forwards to the inherent method of the same name
+impl<T: Clone + PartialEq> Set<T> for NaiveSet<T> {
+    fn new(input: &[T]) -> Self {
+        NaiveSet::new(input)
+    }
+
+    fn contains(&self, element: &T) -> bool {
+        NaiveSet::contains(self, element)
+    }
+
+    fn add(&mut self, element: T) -> bool {
+        NaiveSet::add(self, element)
+    }
+
+    fn remove(&mut self, element: &T) -> bool {
+        NaiveSet::remove(self, element)
+    }
+
+    fn len(&self) -> usize {
+        NaiveSet::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        NaiveSet::is_empty(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        NaiveSet::iter(self)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        NaiveSet::is_subset(self, other)
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        NaiveSet::is_disjoint(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        NaiveSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        NaiveSet::difference(self, other)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        NaiveSet::symmetric_difference(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        NaiveSet::union(self, other)
+    }
 }
 
 impl<T: Clone + PartialEq> PartialEq for NaiveSet<T> {
     fn eq(&self, rhs: &Self) -> bool {
-        self.is_subset(rhs) && rhs.is_subset(self)
+        self.len() == rhs.len() && self.is_subset(rhs)
+    }
+}
+
+impl<T: Clone + Eq> Eq for NaiveSet<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for NaiveSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.v.iter()).finish()
+    }
+}
+
+impl<T: Clone + PartialEq> FromIterator<T> for NaiveSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut new = Self::default();
+        new.extend(iter);
+        new
+    }
+}
+
+impl<T: Clone + PartialEq> Extend<T> for NaiveSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for e in iter {
+            self.add(e);
+        }
+    }
+}
+
+impl<T> IntoIterator for NaiveSet<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.v.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NaiveSet<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.v.iter()
+    }
+}
+
+impl<T: Clone + PartialEq> BitOr<&NaiveSet<T>> for &NaiveSet<T> {
+    type Output = NaiveSet<T>;
+
+    fn bitor(self, rhs: &NaiveSet<T>) -> NaiveSet<T> {
+        self.union(rhs)
+    }
+}
+
+impl<T: Clone + PartialEq> BitAnd<&NaiveSet<T>> for &NaiveSet<T> {
+    type Output = NaiveSet<T>;
+
+    fn bitand(self, rhs: &NaiveSet<T>) -> NaiveSet<T> {
+        self.intersection(rhs)
+    }
+}
+
+impl<T: Clone + PartialEq> Sub<&NaiveSet<T>> for &NaiveSet<T> {
+    type Output = NaiveSet<T>;
+
+    fn sub(self, rhs: &NaiveSet<T>) -> NaiveSet<T> {
+        self.difference(rhs)
+    }
+}
+
+impl<T: Clone + PartialEq> BitXor<&NaiveSet<T>> for &NaiveSet<T> {
+    type Output = NaiveSet<T>;
+
+    fn bitxor(self, rhs: &NaiveSet<T>) -> NaiveSet<T> {
+        self.symmetric_difference(rhs)
     }
 }
@@ -0,0 +1,745 @@
+use crate::Set;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Bound, RangeBounds, Sub};
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone)]
+struct Node<T> {
+    e: T,
+    left: Link<T>,
+    right: Link<T>,
+    height: usize,
+    /// Number of elements in this subtree
+    size: usize,
+}
+
+fn height<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.height)
+}
+
+fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+impl<T> Node<T> {
+    fn new(e: T, left: Link<T>, right: Link<T>) -> Box<Self> {
+        let mut node = Box::new(Node {
+            e,
+            left,
+            right,
+            height: 0,
+            size: 0,
+        });
+        node.update();
+        node
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut left = node.left.take().expect("rotating right needs a left child");
+    node.left = left.right.take();
+    node.update();
+    left.right = Some(node);
+    left.update();
+    left
+}
+
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut right = node
+        .right
+        .take()
+        .expect("rotating left needs a right child");
+    node.right = right.left.take();
+    node.update();
+    right.left = Some(node);
+    right.update();
+    right
+}
+
+/// Restores the AVL property at `node`, whose subtrees differ in height by at most two
+fn rebalance<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    node.update();
+    let (l, r) = (height(&node.left), height(&node.right));
+    if l > r + 1 {
+        let left = node.left.take().unwrap();
+        node.left = Some(if height(&left.left) < height(&left.right) {
+            rotate_left(left)
+        } else {
+            left
+        });
+        rotate_right(node)
+    } else if r > l + 1 {
+        let right = node.right.take().unwrap();
+        node.right = Some(if height(&right.right) < height(&right.left) {
+            rotate_right(right)
+        } else {
+            right
+        });
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+/// A tree holding `left`, then the lone node `mid`, then `right`, where everything in
+/// `left` is less than `mid` and everything in `right` greater. Reuses every node it is
+/// given rather than allocating.
+/// O(|h(left) - h(right)|)
+fn join<T>(left: Link<T>, mut mid: Box<Node<T>>, right: Link<T>) -> Box<Node<T>> {
+    let (l, r) = (height(&left), height(&right));
+    if l > r + 1 {
+        let mut left = left.unwrap();
+        left.right = Some(join(left.right.take(), mid, right));
+        rebalance(left)
+    } else if r > l + 1 {
+        let mut right = right.unwrap();
+        right.left = Some(join(left, mid, right.left.take()));
+        rebalance(right)
+    } else {
+        mid.left = left;
+        mid.right = right;
+        mid.update();
+        mid
+    }
+}
+
+/// Takes the greatest element out of a tree, as a lone node
+/// O(log n)
+fn pop_last<T>(mut node: Box<Node<T>>) -> (Link<T>, Box<Node<T>>) {
+    match node.right.take() {
+        None => {
+            let left = node.left.take();
+            (left, node)
+        }
+        Some(right) => {
+            let (right, last) = pop_last(right);
+            node.right = right;
+            (Some(rebalance(node)), last)
+        }
+    }
+}
+
+/// Joins two trees where everything in `left` is less than everything in `right`
+/// O(log n)
+fn join2<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match left {
+        None => right,
+        Some(left) => {
+            let (left, last) = pop_last(left);
+            Some(join(left, last, right))
+        }
+    }
+}
+
+/// The elements less than `key`, the lone node holding `key` if it's there, and the
+/// elements greater
+/// O(log n)
+fn split<T: Ord>(link: Link<T>, key: &T) -> (Link<T>, Option<Box<Node<T>>>, Link<T>) {
+    let Some(mut node) = link else {
+        return (None, None, None);
+    };
+    let (left, right) = (node.left.take(), node.right.take());
+    match key.cmp(&node.e) {
+        Ordering::Less => {
+            let (less, found, greater) = split(left, key);
+            (less, found, Some(join(greater, node, right)))
+        }
+        Ordering::Greater => {
+            let (less, found, greater) = split(right, key);
+            (Some(join(left, node, less)), found, greater)
+        }
+        Ordering::Equal => (left, Some(node), right),
+    }
+}
+
+/// A perfectly balanced tree of the next `n` elements, which must be sorted
+/// O(n)
+fn build<T>(n: usize, sorted: &mut impl Iterator<Item = T>) -> Link<T> {
+    if n == 0 {
+        return None;
+    }
+    let left = build(n / 2, sorted);
+    let e = sorted.next().expect("the iterator holds n elements");
+    let right = build(n - n / 2 - 1, sorted);
+    Some(Node::new(e, left, right))
+}
+
+/// The Ordered implementation using a balanced binary search tree.
+///
+/// An AVL tree whose nodes also count their subtree, so it can be split and joined in
+/// O(log n). Elements are kept in sorted order, and the set operations merge the two
+/// sorted sequences in one pass.
+#[derive(Clone)]
+pub struct OrderedSet<T> {
+    root: Link<T>,
+}
+
+impl<T> Default for OrderedSet<T> {
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<T: Clone + Ord> OrderedSet<T> {
+    /// O(n log n)
+    pub fn new(input: &[T]) -> Self {
+        input.iter().cloned().collect()
+    }
+
+    /// A set of elements that are already sorted with no repeats
+    /// O(n)
+    fn from_sorted(sorted: Vec<T>) -> Self {
+        Self {
+            root: build(sorted.len(), &mut sorted.into_iter()),
+        }
+    }
+
+    /// O(log n)
+    pub fn contains(&self, element: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match element.cmp(&node.e) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// True if the element wasn't already there.
+    /// O(log n)
+    pub fn add(&mut self, e: T) -> bool {
+        if self.contains(&e) {
+            return false;
+        }
+        let (less, _, greater) = split(self.root.take(), &e);
+        self.root = Some(join(less, Node::new(e, None, None), greater));
+        true
+    }
+
+    /// True if the element was there.
+    /// O(log n)
+    pub fn remove(&mut self, element: &T) -> bool {
+        if !self.contains(element) {
+            return false;
+        }
+        let (less, _, greater) = split(self.root.take(), element);
+        self.root = join2(less, greater);
+        true
+    }
+
+    /// O(1)
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// The smallest element
+    /// O(log n)
+    pub fn first(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        Some(&node.e)
+    }
+
+    /// The greatest element
+    /// O(log n)
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(&node.e)
+    }
+
+    /// Moves every element from `key` up into a new set
+    /// O(log n)
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let (less, found, greater) = split(self.root.take(), key);
+        self.root = less;
+        let greater = match found {
+            Some(node) => Some(join(None, node, greater)),
+            None => greater,
+        };
+        Self { root: greater }
+    }
+
+    /// Moves every element of `other` into this set, leaving `other` empty.
+    /// O(log n) if all of one set is less than all of the other, O(n + m) otherwise.
+    pub fn append(&mut self, other: &mut Self) {
+        let other = std::mem::take(other);
+        match (self.last(), other.first()) {
+            (_, None) => {}
+            (None, _) => *self = other,
+            (Some(last), Some(first)) if last < first => {
+                self.root = join2(self.root.take(), other.root);
+            }
+            _ if other.last() < self.first() => {
+                self.root = join2(other.root, self.root.take());
+            }
+            _ => *self = self.union(&other),
+        }
+    }
+
+    /// O(n + m)
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.intersection_len(other) == self.len()
+    }
+
+    /// O(n + m)
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection_len(other) == 0
+    }
+
+    fn intersection_len(&self, other: &Self) -> usize {
+        merge(self, other)
+            .filter(|m| matches!(m, Merged::Both(_)))
+            .count()
+    }
+
+    /// O(n + m)
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.merged(other, |m| match m {
+            Merged::Both(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    /// O(n + m)
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.merged(other, |m| match m {
+            Merged::Left(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    /// O(n + m)
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.merged(other, |m| match m {
+            Merged::Left(e) | Merged::Right(e) => Some(e),
+            Merged::Both(_) => None,
+        })
+    }
+
+    /// O(n + m)
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.merged(other, |m| match m {
+            Merged::Left(e) | Merged::Right(e) | Merged::Both(e) => Some(e),
+        })
+    }
+
+    /// The elements `keep` picks out of the merge of the two sets
+    fn merged<'a>(
+        &'a self,
+        other: &'a Self,
+        keep: impl Fn(Merged<&'a T>) -> Option<&'a T>,
+    ) -> Self {
+        Self::from_sorted(merge(self, other).filter_map(keep).cloned().collect())
+    }
+
+    /// Every element in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.range(..)
+    }
+
+    /// The elements within `range`, in sorted order.
+    /// O(log n) to find the start, then O(1) amortised per element.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Iter<'_, T> {
+        let mut stack = Vec::new();
+        let mut link = &self.root;
+        while let Some(node) = link {
+            let below = match range.start_bound() {
+                Bound::Included(start) => node.e < *start,
+                Bound::Excluded(start) => node.e <= *start,
+                Bound::Unbounded => false,
+            };
+            link = if below {
+                &node.right
+            } else {
+                stack.push(node.as_ref());
+                &node.left
+            };
+        }
+        Iter {
+            stack,
+            end: range.end_bound().cloned(),
+        }
+    }
+}
+
+/// Each method forwards to the inherent method of the same name
+impl<T: Clone + Ord> Set<T> for OrderedSet<T> {
+    fn new(input: &[T]) -> Self {
+        OrderedSet::new(input)
+    }
+
+    fn contains(&self, element: &T) -> bool {
+        OrderedSet::contains(self, element)
+    }
+
+    fn add(&mut self, element: T) -> bool {
+        OrderedSet::add(self, element)
+    }
+
+    fn remove(&mut self, element: &T) -> bool {
+        OrderedSet::remove(self, element)
+    }
+
+    fn len(&self) -> usize {
+        OrderedSet::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        OrderedSet::is_empty(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        OrderedSet::iter(self)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        OrderedSet::is_subset(self, other)
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        OrderedSet::is_disjoint(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        OrderedSet::intersection(self, other)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        OrderedSet::difference(self, other)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        OrderedSet::symmetric_difference(self, other)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        OrderedSet::union(self, other)
+    }
+}
+
+/// Where an element turned up when merging two sorted sets
+enum Merged<T> {
+    Left(T),
+    Right(T),
+    Both(T),
+}
+
+/// Walks two sets side by side in sorted order
+/// O(n + m)
+fn merge<'a, T: Clone + Ord>(
+    left: &'a OrderedSet<T>,
+    right: &'a OrderedSet<T>,
+) -> impl Iterator<Item = Merged<&'a T>> {
+    let mut left = left.iter().peekable();
+    let mut right = right.iter().peekable();
+    std::iter::from_fn(move || match (left.peek(), right.peek()) {
+        (None, None) => None,
+        (Some(_), None) => left.next().map(Merged::Left),
+        (None, Some(_)) => right.next().map(Merged::Right),
+        (Some(l), Some(r)) => match l.cmp(r) {
+            Ordering::Less => left.next().map(Merged::Left),
+            Ordering::Greater => right.next().map(Merged::Right),
+            Ordering::Equal => {
+                right.next();
+                left.next().map(Merged::Both)
+            }
+        },
+    })
+}
+
+/// Borrows the elements of an `OrderedSet`, or of a range of it, in sorted order
+pub struct Iter<'a, T> {
+    /// The nodes still to visit along with their right subtrees, next one on top
+    stack: Vec<&'a Node<T>>,
+    end: Bound<T>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        let past_end = match &self.end {
+            Bound::Included(end) => node.e > *end,
+            Bound::Excluded(end) => node.e >= *end,
+            Bound::Unbounded => false,
+        };
+        if past_end {
+            self.stack.clear();
+            return None;
+        }
+        let mut link = &node.right;
+        while let Some(next) = link {
+            self.stack.push(next);
+            link = &next.left;
+        }
+        Some(&node.e)
+    }
+}
+
+/// Takes the elements out of an `OrderedSet` in sorted order
+pub struct IntoIter<T> {
+    elements: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.elements.next()
+    }
+}
+
+/// Moves the elements of a tree onto the end of `out` in order
+fn drain<T>(link: Link<T>, out: &mut Vec<T>) {
+    if let Some(node) = link {
+        let Node { e, left, right, .. } = *node;
+        drain(left, out);
+        out.push(e);
+        drain(right, out);
+    }
+}
+
+impl<T: Clone + Ord> PartialEq for OrderedSet<T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.len() == rhs.len() && self.iter().eq(rhs.iter())
+    }
+}
+
+impl<T: Clone + Ord> Eq for OrderedSet<T> {}
+
+impl<T: Clone + Ord + fmt::Debug> fmt::Debug for OrderedSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone + Ord> FromIterator<T> for OrderedSet<T> {
+    /// O(n log n)
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sorted: Vec<T> = iter.into_iter().collect();
+        sorted.sort();
+        sorted.dedup();
+        Self::from_sorted(sorted)
+    }
+}
+
+impl<T: Clone + Ord> Extend<T> for OrderedSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for e in iter {
+            self.add(e);
+        }
+    }
+}
+
+impl<T> IntoIterator for OrderedSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let mut elements = Vec::with_capacity(size(&self.root));
+        drain(self.root, &mut elements);
+        IntoIter {
+            elements: elements.into_iter(),
+        }
+    }
+}
+
+impl<'a, T: Clone + Ord> IntoIterator for &'a OrderedSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Clone + Ord> BitOr<&OrderedSet<T>> for &OrderedSet<T> {
+    type Output = OrderedSet<T>;
+
+    fn bitor(self, rhs: &OrderedSet<T>) -> OrderedSet<T> {
+        self.union(rhs)
+    }
+}
+
+impl<T: Clone + Ord> BitAnd<&OrderedSet<T>> for &OrderedSet<T> {
+    type Output = OrderedSet<T>;
+
+    fn bitand(self, rhs: &OrderedSet<T>) -> OrderedSet<T> {
+        self.intersection(rhs)
+    }
+}
+
+impl<T: Clone + Ord> Sub<&OrderedSet<T>> for &OrderedSet<T> {
+    type Output = OrderedSet<T>;
+
+    fn sub(self, rhs: &OrderedSet<T>) -> OrderedSet<T> {
+        self.difference(rhs)
+    }
+}
+
+impl<T: Clone + Ord> BitXor<&OrderedSet<T>> for &OrderedSet<T> {
+    type Output = OrderedSet<T>;
+
+    fn bitxor(self, rhs: &OrderedSet<T>) -> OrderedSet<T> {
+        self.symmetric_difference(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The height and size of a tree, checking it is ordered and balanced on the way
+    fn check<T: Ord>(link: &Link<T>, low: Option<&T>, high: Option<&T>) -> (usize, usize) {
+        let Some(node) = link else {
+            return (0, 0);
+        };
+        assert!(low.is_none_or(|low| *low < node.e));
+        assert!(high.is_none_or(|high| node.e < *high));
+        let (lh, ls) = check(&node.left, low, Some(&node.e));
+        let (rh, rs) = check(&node.right, Some(&node.e), high);
+        assert!(lh.abs_diff(rh) <= 1, "unbalanced");
+        assert_eq!(node.height, 1 + lh.max(rh));
+        assert_eq!(node.size, 1 + ls + rs);
+        (node.height, node.size)
+    }
+
+    fn valid<T: Ord>(set: &OrderedSet<T>) -> bool {
+        check(&set.root, None, None);
+        true
+    }
+
+    fn elements(set: &OrderedSet<i32>) -> Vec<i32> {
+        set.iter().copied().collect()
+    }
+
+    #[test]
+    fn stays_sorted_and_balanced() {
+        let mut set = OrderedSet::new(&[]);
+        for i in (0..200).rev().chain(200..400) {
+            assert!(set.add(i * 7 % 400));
+        }
+        assert!(valid(&set));
+        assert_eq!(elements(&set), (0..400).collect::<Vec<_>>());
+        for i in (0..400).step_by(3) {
+            assert!(set.remove(&i));
+        }
+        assert!(valid(&set));
+        assert_eq!(
+            elements(&set),
+            (0..400).filter(|i| i % 3 != 0).collect::<Vec<_>>()
+        );
+        assert_eq!(set.len(), 266);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let set = OrderedSet::new(&[5, 1, 9, 3]);
+        assert_eq!(set.first(), Some(&1));
+        assert_eq!(set.last(), Some(&9));
+        assert_eq!(OrderedSet::<i32>::new(&[]).first(), None);
+    }
+
+    #[test]
+    fn ranges() {
+        let set: OrderedSet<i32> = (0..20).map(|i| i * 2).collect();
+        let range = |r: (Bound<i32>, Bound<i32>)| set.range(r).copied().collect::<Vec<_>>();
+        assert_eq!(
+            set.range(5..11).copied().collect::<Vec<_>>(),
+            vec![6, 8, 10]
+        );
+        assert_eq!(
+            set.range(6..=10).copied().collect::<Vec<_>>(),
+            vec![6, 8, 10]
+        );
+        assert_eq!(set.range(..4).copied().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(set.range(35..).copied().collect::<Vec<_>>(), vec![36, 38]);
+        assert_eq!(
+            range((Bound::Excluded(6), Bound::Excluded(12))),
+            vec![8, 10]
+        );
+        assert_eq!(set.range(11..12).count(), 0);
+        assert_eq!(set.range(100..).count(), 0);
+    }
+
+    #[test]
+    fn split_off_and_append() {
+        let mut low: OrderedSet<i32> = (0..100).collect();
+        let mut high = low.split_off(&60);
+        assert!(valid(&low) && valid(&high));
+        assert_eq!(elements(&low), (0..60).collect::<Vec<_>>());
+        assert_eq!(elements(&high), (60..100).collect::<Vec<_>>());
+        assert_eq!((low.len(), high.len()), (60, 40));
+
+        let mut missing_key = high.split_off(&1000);
+        assert!(missing_key.is_empty());
+        assert_eq!(high.len(), 40);
+
+        high.append(&mut low);
+        assert!(valid(&high) && low.is_empty());
+        assert_eq!(elements(&high), (0..100).collect::<Vec<_>>());
+
+        let mut evens: OrderedSet<i32> = (0..10).map(|i| i * 2).collect();
+        evens.append(&mut (5..15).collect());
+        assert!(valid(&evens));
+        assert_eq!(
+            elements(&evens),
+            vec![0, 2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 16, 18]
+        );
+        evens.append(&mut missing_key);
+        assert_eq!(evens.len(), 15);
+    }
+
+    #[test]
+    fn set_operations_merge_in_order() {
+        let a: OrderedSet<i32> = (0..10).collect();
+        let b: OrderedSet<i32> = (5..15).collect();
+        assert_eq!(elements(&(&a | &b)), (0..15).collect::<Vec<_>>());
+        assert_eq!(elements(&(&a & &b)), (5..10).collect::<Vec<_>>());
+        assert_eq!(elements(&(&a - &b)), (0..5).collect::<Vec<_>>());
+        assert_eq!(
+            elements(&(&a ^ &b)),
+            (0..5).chain(10..15).collect::<Vec<_>>()
+        );
+        assert!(valid(&(&a | &b)));
+        assert!((&a & &b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!((&a - &b).is_disjoint(&b));
+    }
+
+    #[test]
+    fn into_iter_is_sorted() {
+        let set = OrderedSet::new(&[3, 1, 2]);
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
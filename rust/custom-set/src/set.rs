@@ -0,0 +1,41 @@
+/// The operations every set implementation provides, so code and tests can be written
+/// once for all of them
+pub trait Set<T>: Sized + PartialEq {
+    /// A set of the distinct elements of `input`
+    fn new(input: &[T]) -> Self;
+
+    fn contains(&self, element: &T) -> bool;
+
+    /// True if the element wasn't already there
+    fn add(&mut self, element: T) -> bool;
+
+    /// True if the element was there
+    fn remove(&mut self, element: &T) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every element, in an order that depends on the implementation
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
+
+    fn is_subset(&self, other: &Self) -> bool;
+
+    fn is_disjoint(&self, other: &Self) -> bool;
+
+    #[must_use]
+    fn intersection(&self, other: &Self) -> Self;
+
+    #[must_use]
+    fn difference(&self, other: &Self) -> Self;
+
+    #[must_use]
+    fn symmetric_difference(&self, other: &Self) -> Self;
+
+    #[must_use]
+    fn union(&self, other: &Self) -> Self;
+}
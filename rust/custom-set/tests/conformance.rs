@@ -0,0 +1,140 @@
+//! Properties every `Set` implementation must have, checked against `BTreeSet` on
+//! randomly generated inputs. A new implementation only needs a `conformance!` line at
+//! the bottom to be checked.
+
+use custom_set::{FastSet, NaiveSet, OrderedSet, Set};
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+
+/// Elements come from a small range so that sets overlap and repeats are common
+fn element() -> impl Strategy<Value = i32> {
+    0..40
+}
+
+fn elements() -> impl Strategy<Value = Vec<i32>> {
+    prop::collection::vec(element(), 0..30)
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Add(i32),
+    Remove(i32),
+}
+
+fn ops() -> impl Strategy<Value = Vec<Op>> {
+    let op = prop_oneof![element().prop_map(Op::Add), element().prop_map(Op::Remove)];
+    prop::collection::vec(op, 0..100)
+}
+
+fn model<S: Set<i32>>(set: &S) -> BTreeSet<i32> {
+    set.iter().copied().collect()
+}
+
+fn same_as<S: Set<i32>>(set: &S, expected: &BTreeSet<i32>) -> Result<(), TestCaseError> {
+    let elements: Vec<i32> = set.iter().copied().collect();
+    prop_assert_eq!(elements.len(), expected.len(), "repeated elements");
+    prop_assert_eq!(&model(set), expected);
+    prop_assert_eq!(set.len(), expected.len());
+    prop_assert_eq!(set.is_empty(), expected.is_empty());
+    for e in -1..41 {
+        prop_assert_eq!(set.contains(&e), expected.contains(&e), "contains({})", e);
+    }
+    Ok(())
+}
+
+fn new_holds_the_distinct_elements<S: Set<i32>>(input: &[i32]) -> Result<(), TestCaseError> {
+    same_as(&S::new(input), &input.iter().copied().collect())
+}
+
+fn adds_and_removes_match_the_model<S: Set<i32>>(ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut set = S::new(&[]);
+    let mut expected = BTreeSet::new();
+    for op in ops {
+        match *op {
+            Op::Add(e) => prop_assert_eq!(set.add(e), expected.insert(e)),
+            Op::Remove(e) => prop_assert_eq!(set.remove(&e), expected.remove(&e)),
+        }
+        prop_assert_eq!(set.len(), expected.len());
+    }
+    same_as(&set, &expected)
+}
+
+fn operations_match_the_model<S: Set<i32> + Debug>(
+    a: &[i32],
+    b: &[i32],
+) -> Result<(), TestCaseError> {
+    let (set_a, set_b) = (S::new(a), S::new(b));
+    let (model_a, model_b): (BTreeSet<i32>, BTreeSet<i32>) =
+        (a.iter().copied().collect(), b.iter().copied().collect());
+
+    same_as(&set_a.union(&set_b), &(&model_a | &model_b))?;
+    same_as(&set_a.intersection(&set_b), &(&model_a & &model_b))?;
+    same_as(&set_a.difference(&set_b), &(&model_a - &model_b))?;
+    same_as(&set_a.symmetric_difference(&set_b), &(&model_a ^ &model_b))?;
+    prop_assert_eq!(set_a.is_subset(&set_b), model_a.is_subset(&model_b));
+    prop_assert_eq!(set_a.is_disjoint(&set_b), model_a.is_disjoint(&model_b));
+    prop_assert_eq!(set_a == set_b, model_a == model_b);
+    Ok(())
+}
+
+fn operations_obey_the_set_laws<S: Set<i32> + Debug>(
+    a: &[i32],
+    b: &[i32],
+) -> Result<(), TestCaseError> {
+    let (a, b) = (S::new(a), S::new(b));
+    let union = a.union(&b);
+    let intersection = a.intersection(&b);
+
+    prop_assert_eq!(&union, &b.union(&a));
+    prop_assert_eq!(&intersection, &b.intersection(&a));
+    prop_assert!(a.is_subset(&union) && b.is_subset(&union));
+    prop_assert!(intersection.is_subset(&a) && intersection.is_subset(&b));
+    prop_assert!(a.difference(&b).is_disjoint(&b));
+    prop_assert_eq!(
+        a.symmetric_difference(&b),
+        a.difference(&b).union(&b.difference(&a))
+    );
+    prop_assert_eq!(
+        union.len() + intersection.len(),
+        a.len() + b.len(),
+        "inclusion-exclusion"
+    );
+    prop_assert!(a.is_subset(&a));
+    prop_assert_eq!(a.is_disjoint(&a), a.is_empty());
+    Ok(())
+}
+
+macro_rules! conformance {
+    ($name:ident, $set:ty) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn new_holds_the_distinct_elements(input in elements()) {
+                    super::new_holds_the_distinct_elements::<$set>(&input)?;
+                }
+
+                #[test]
+                fn adds_and_removes_match_the_model(ops in ops()) {
+                    super::adds_and_removes_match_the_model::<$set>(&ops)?;
+                }
+
+                #[test]
+                fn operations_match_the_model(a in elements(), b in elements()) {
+                    super::operations_match_the_model::<$set>(&a, &b)?;
+                }
+
+                #[test]
+                fn operations_obey_the_set_laws(a in elements(), b in elements()) {
+                    super::operations_obey_the_set_laws::<$set>(&a, &b)?;
+                }
+            }
+        }
+    };
+}
+
+conformance!(naive, NaiveSet<i32>);
+conformance!(fast, FastSet<i32>);
+conformance!(ordered, OrderedSet<i32>);
@@ -0,0 +1,101 @@
+//! The API beyond the exercise, checked against every implementation.
+
+use custom_set::{FastSet, NaiveSet, OrderedSet};
+
+macro_rules! set_api_tests {
+    ($name:ident, $set:ty) => {
+        mod $name {
+            use super::*;
+
+            type Set<T> = $set;
+
+            fn sorted(set: &Set<i32>) -> Vec<i32> {
+                let mut v: Vec<i32> = set.iter().copied().collect();
+                v.sort_unstable();
+                v
+            }
+
+            #[test]
+            fn duplicates_are_only_counted_once() {
+                let set = Set::new(&[1, 2, 2, 3, 1]);
+                assert_eq!(set.len(), 3);
+                assert_eq!(sorted(&set), vec![1, 2, 3]);
+            }
+
+            #[test]
+            fn add_reports_whether_the_element_is_new() {
+                let mut set = Set::new(&[1]);
+                assert!(set.add(2));
+                assert!(!set.add(1));
+                assert_eq!(set.len(), 2);
+            }
+
+            #[test]
+            fn remove_takes_an_element_out() {
+                let mut set = Set::new(&[1, 2, 3]);
+                assert!(set.remove(&2));
+                assert!(!set.remove(&2));
+                assert!(!set.contains(&2));
+                assert_eq!(set, Set::new(&[3, 1]));
+                set.clear();
+                assert!(set.is_empty());
+            }
+
+            #[test]
+            fn collects_and_extends_from_iterators() {
+                let mut set: Set<i32> = (1..=3).chain(2..=4).collect();
+                assert_eq!(sorted(&set), vec![1, 2, 3, 4]);
+                set.extend(vec![4, 5]);
+                assert_eq!(sorted(&set), vec![1, 2, 3, 4, 5]);
+            }
+
+            #[test]
+            fn iterates_by_reference_and_by_value() {
+                let set = Set::new(&[1, 2, 3]);
+                let mut total = 0;
+                for e in &set {
+                    total += e;
+                }
+                assert_eq!(total, 6);
+
+                let mut owned: Vec<i32> = set.into_iter().collect();
+                owned.sort_unstable();
+                assert_eq!(owned, vec![1, 2, 3]);
+            }
+
+            #[test]
+            fn debug_prints_like_a_set() {
+                assert_eq!(format!("{:?}", Set::new(&[7])), "{7}");
+                assert_eq!(format!("{:?}", Set::<i32>::new(&[])), "{}");
+            }
+
+            #[test]
+            fn symmetric_difference_is_elements_in_exactly_one_set() {
+                let a = Set::new(&[1, 2, 3]);
+                let b = Set::new(&[3, 4]);
+                assert_eq!(a.symmetric_difference(&b), Set::new(&[1, 2, 4]));
+                assert_eq!(a.symmetric_difference(&a), Set::new(&[]));
+            }
+
+            #[test]
+            fn operators_match_the_methods() {
+                let a = Set::new(&[1, 2, 3]);
+                let b = Set::new(&[2, 3, 4]);
+                assert_eq!(&a | &b, Set::new(&[1, 2, 3, 4]));
+                assert_eq!(&a & &b, Set::new(&[2, 3]));
+                assert_eq!(&a - &b, Set::new(&[1]));
+                assert_eq!(&a ^ &b, Set::new(&[1, 4]));
+            }
+
+            #[test]
+            fn sets_of_different_sizes_are_not_equal() {
+                assert_ne!(Set::new(&[1, 2]), Set::new(&[1, 2, 3]));
+                assert_ne!(Set::new(&[1, 2, 3]), Set::new(&[1, 2]));
+            }
+        }
+    };
+}
+
+set_api_tests!(naive, NaiveSet<T>);
+set_api_tests!(fast, FastSet<T>);
+set_api_tests!(ordered, OrderedSet<T>);
@@ -0,0 +1,393 @@
+//! Queries over a [Graph]. Directed edges are followed one way and undirected edges both
+//! ways. Edge ends that aren't among the graph's nodes are skipped.
+
+use crate::graph::graph_items::{edge::Edge, node::Node};
+use crate::graph::Graph;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// A node and its neighbours, with the edge to each, in the order the edges were added
+struct Neighbours<'a> {
+    node: &'a Node,
+    next: Vec<(&'a str, &'a Edge)>,
+}
+
+type Adjacency<'a> = HashMap<&'a str, Neighbours<'a>>;
+
+/// Nodes in the order a breadth first search reaches them
+pub struct Bfs<'a> {
+    adjacency: Adjacency<'a>,
+    queue: VecDeque<&'a str>,
+    seen: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for Bfs<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let neighbours = &self.adjacency[self.queue.pop_front()?];
+        for &(next, _) in &neighbours.next {
+            if self.seen.insert(next) {
+                self.queue.push_back(next);
+            }
+        }
+        Some(neighbours.node)
+    }
+}
+
+/// Nodes in the order a depth first search first visits them
+pub struct Dfs<'a> {
+    adjacency: Adjacency<'a>,
+    stack: Vec<&'a str>,
+    seen: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for Dfs<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let name = loop {
+            let name = self.stack.pop()?;
+            if self.seen.insert(name) {
+                break name;
+            }
+        };
+        let neighbours = &self.adjacency[name];
+        let unseen: Vec<&str> = neighbours
+            .next
+            .iter()
+            .map(|&(next, _)| next)
+            .filter(|next| !self.seen.contains(next))
+            .collect();
+        self.stack.extend(unseen.iter().rev());
+        Some(neighbours.node)
+    }
+}
+
+/// Nodes that lead back round to the first of them, which stops a topological sort
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cycle<'a>(pub Vec<&'a Node>);
+
+/// A cheapest route and what it costs
+#[derive(Debug, PartialEq)]
+pub struct Path<'a> {
+    pub cost: f64,
+    pub nodes: Vec<&'a Node>,
+}
+
+/// A weight attribute that isn't a finite number of at least 0
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidWeight<'a> {
+    pub edge: &'a Edge,
+    pub value: &'a str,
+}
+
+/// A node still to visit in Dijkstra's algorithm, cheapest first in a [BinaryHeap]
+struct Visit<'a> {
+    cost: f64,
+    node: &'a str,
+}
+
+impl PartialEq for Visit<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Visit<'_> {}
+
+impl PartialOrd for Visit<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl Graph {
+    /// Looks up a node by name without cloning it
+    /// O(1)
+    pub fn node(&self, name: &str) -> Option<&Node> {
+        self.position(name).map(|i| &self.nodes[i])
+    }
+
+    /// O(n + e)
+    fn adjacency(&self, undirected: bool) -> Adjacency<'_> {
+        let mut adjacency: Adjacency = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let next = Vec::new();
+                (node.name(), Neighbours { node, next })
+            })
+            .collect();
+        for edge in &self.edges {
+            let (from, to) = edge.vertices();
+            if !adjacency.contains_key(from) || !adjacency.contains_key(to) {
+                continue;
+            }
+            adjacency.get_mut(from).unwrap().next.push((to, edge));
+            if undirected || !edge.is_directed() {
+                adjacency.get_mut(to).unwrap().next.push((from, edge));
+            }
+        }
+        adjacency
+    }
+
+    /// Breadth first from `start`, which comes first. Empty if there is no such node.
+    /// O(n + e)
+    pub fn bfs(&self, start: &str) -> Bfs<'_> {
+        let start = self.node(start).map(|n| n.name());
+        Bfs {
+            adjacency: self.adjacency(false),
+            queue: start.into_iter().collect(),
+            seen: start.into_iter().collect(),
+        }
+    }
+
+    /// Depth first from `start`, which comes first. Empty if there is no such node.
+    /// O(n + e)
+    pub fn dfs(&self, start: &str) -> Dfs<'_> {
+        let start = self.node(start).map(|n| n.name());
+        Dfs {
+            adjacency: self.adjacency(false),
+            stack: start.into_iter().collect(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Orders the nodes so every directed edge goes forwards, keeping nodes in the order
+    /// they were added where the edges allow. Undirected edges don't constrain the order.
+    /// O(n log n + e)
+    pub fn topological_sort(&self) -> Result<Vec<&Node>, Cycle<'_>> {
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        let mut incoming = vec![0; self.nodes.len()];
+        for edge in self.edges.iter().filter(|e| e.is_directed()) {
+            let (from, to) = edge.vertices();
+            if let (Some(from), Some(to)) = (self.position(from), self.position(to)) {
+                successors[from].push(to);
+                incoming[to] += 1;
+            }
+        }
+
+        // Kahn's algorithm, always taking the earliest added node that is ready
+        let mut ready: BinaryHeap<std::cmp::Reverse<usize>> = (0..self.nodes.len())
+            .filter(|&i| incoming[i] == 0)
+            .map(std::cmp::Reverse)
+            .collect();
+        let mut order = Vec::new();
+        while let Some(std::cmp::Reverse(i)) = ready.pop() {
+            order.push(&self.nodes[i]);
+            for &next in &successors[i] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(std::cmp::Reverse(next));
+                }
+            }
+        }
+        if order.len() == self.nodes.len() {
+            return Ok(order);
+        }
+
+        // Every node left has an edge in from another node left, so walking those edges
+        // backwards from any of them must come round to a node already walked through
+        let mut predecessor = vec![None; self.nodes.len()];
+        for (from, tos) in successors.iter().enumerate() {
+            for &to in tos {
+                if incoming[from] > 0 && incoming[to] > 0 {
+                    predecessor[to] = Some(from);
+                }
+            }
+        }
+        let mut walked = Vec::new();
+        let mut at = (0..self.nodes.len()).find(|&i| incoming[i] > 0).unwrap();
+        while !walked.contains(&at) {
+            walked.push(at);
+            at = predecessor[at].unwrap();
+        }
+        let start = walked.iter().position(|&i| i == at).unwrap();
+        let mut cycle: Vec<&Node> = walked[start..]
+            .iter()
+            .rev()
+            .map(|&i| &self.nodes[i])
+            .collect();
+        cycle.rotate_right(1);
+        Err(Cycle(cycle))
+    }
+
+    /// Groups of nodes joined by edges in either direction, in the order nodes were added
+    /// O(n + e)
+    pub fn connected_components(&self) -> Vec<Vec<&Node>> {
+        let adjacency = self.adjacency(true);
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for node in &self.nodes {
+            if !seen.insert(node.name()) {
+                continue;
+            }
+            let mut component = vec![node];
+            let mut queue = VecDeque::from(vec![node.name()]);
+            while let Some(name) = queue.pop_front() {
+                for &(next, _) in &adjacency[name].next {
+                    if seen.insert(next) {
+                        component.push(adjacency[next].node);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// The cheapest path from `from` to `to` by Dijkstra's algorithm, with each edge costing
+    /// its `weight` attribute, or 1 without one. `Ok(None)` if there is no path.
+    /// O((n + e) log n)
+    pub fn shortest_path(
+        &self,
+        from: &str,
+        to: &str,
+        weight: &str,
+    ) -> Result<Option<Path<'_>>, InvalidWeight<'_>> {
+        for edge in &self.edges {
+            if let Some(value) = edge.get_attr(weight) {
+                match value.parse::<f64>() {
+                    Ok(w) if w.is_finite() && w >= 0.0 => {}
+                    _ => return Err(InvalidWeight { edge, value }),
+                }
+            }
+        }
+        let cost_of = |edge: &Edge| edge.get_attr(weight).map_or(1.0, |w| w.parse().unwrap());
+
+        let (start, goal) = match (self.node(from), self.node(to)) {
+            (Some(start), Some(goal)) => (start.name(), goal.name()),
+            _ => return Ok(None),
+        };
+        let adjacency = self.adjacency(false);
+        let mut best: HashMap<&str, (f64, Option<&str>)> = HashMap::new();
+        best.insert(start, (0.0, None));
+        let mut heap = BinaryHeap::new();
+        heap.push(Visit {
+            cost: 0.0,
+            node: start,
+        });
+
+        while let Some(Visit { cost, node }) = heap.pop() {
+            if node == goal {
+                let mut nodes = vec![adjacency[goal].node];
+                let mut at = goal;
+                while let Some(&(_, Some(prev))) = best.get(at) {
+                    nodes.push(adjacency[prev].node);
+                    at = prev;
+                }
+                nodes.reverse();
+                return Ok(Some(Path { cost, nodes }));
+            }
+            if cost > best[node].0 {
+                continue;
+            }
+            for &(next, edge) in &adjacency[node].next {
+                let cost = cost + cost_of(edge);
+                if best.get(next).is_none_or(|&(c, _)| cost < c) {
+                    best.insert(next, (cost, Some(node)));
+                    heap.push(Visit { cost, node: next });
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> Vec<&'a str> {
+        nodes.into_iter().map(|n| n.name()).collect()
+    }
+
+    fn graph(dot: &str) -> Graph {
+        Graph::from_dot(dot).unwrap()
+    }
+
+    #[test]
+    fn node_lookup_borrows() {
+        let g = graph("graph { a [color=red]; b }");
+        assert_eq!(g.node("a").unwrap().get_attr("color"), Some("red"));
+        assert!(g.node("c").is_none());
+    }
+
+    #[test]
+    fn bfs_and_dfs_follow_edge_directions() {
+        let g = graph("digraph { a -> b; a -> c; b -> d; c -> d; d -> e; f -> a }");
+        assert_eq!(names(g.bfs("a")), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(names(g.dfs("a")), vec!["a", "b", "d", "e", "c"]);
+        assert_eq!(names(g.dfs("e")), vec!["e"]);
+        assert_eq!(names(g.bfs("z")), Vec::<&str>::new());
+
+        let g = graph("graph { a -- b; b -- c; d }");
+        assert_eq!(names(g.bfs("c")), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn topological_sort_keeps_insertion_order() {
+        let g =
+            graph("digraph { shirt; tie; jacket; belt; shirt -> tie -> jacket; belt -> jacket }");
+        assert_eq!(
+            g.topological_sort().map(names),
+            Ok(vec!["shirt", "tie", "belt", "jacket"])
+        );
+    }
+
+    #[test]
+    fn topological_sort_reports_cycles() {
+        let g = graph("digraph { start -> a -> b -> c -> a; c -> end }");
+        let cycle = g.topological_sort().unwrap_err();
+        assert_eq!(names(cycle.0), vec!["a", "b", "c"]);
+
+        let g = graph("digraph { a -> a }");
+        assert_eq!(names(g.topological_sort().unwrap_err().0), vec!["a"]);
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        let g = graph("digraph { a -> b; c -> b; d; e -> f }");
+        let components: Vec<_> = g.connected_components().into_iter().map(names).collect();
+        assert_eq!(
+            components,
+            vec![vec!["a", "b", "c"], vec!["d"], vec!["e", "f"]]
+        );
+    }
+
+    #[test]
+    fn shortest_path_uses_weights() {
+        let g =
+            graph("digraph { a -> b [w=4]; a -> c [w=1]; c -> b [w=1.5]; b -> d; c -> d [w=9] }");
+        let path = g.shortest_path("a", "d", "w").unwrap().unwrap();
+        assert_eq!(path.cost, 3.5);
+        assert_eq!(names(path.nodes), vec!["a", "c", "b", "d"]);
+
+        // without the attribute every edge costs 1
+        let path = g.shortest_path("a", "d", "cost").unwrap().unwrap();
+        assert_eq!(path.cost, 2.0);
+        assert_eq!(names(path.nodes), vec!["a", "b", "d"]);
+
+        assert_eq!(g.shortest_path("d", "a", "w"), Ok(None));
+        assert_eq!(
+            g.shortest_path("a", "a", "w").unwrap().map(|p| p.cost),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn shortest_path_rejects_bad_weights() {
+        let g = graph("graph { a -- b [w=-1]; b -- c [w=heavy] }");
+        let error = g.shortest_path("a", "c", "w").unwrap_err();
+        assert_eq!(error.edge.vertices(), ("a", "b"));
+        assert_eq!(error.value, "-1");
+    }
+}
@@ -0,0 +1,132 @@
+//! Helpers for writing the DOT language.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+const KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+
+/// Writes `s` as a DOT ID, quoting it unless it is a plain identifier or a number.
+/// Inside quotes only `"` is escaped, since DOT leaves every other backslash as it is
+/// for labels like `\n` and `\N`. A run of backslashes right before a `"` or the closing
+/// quote is padded to an even length, so it can't escape the quote.
+pub(crate) fn id(s: &str) -> Cow<'_, str> {
+    if is_identifier(s) || is_numeral(s) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    let mut backslashes = 0;
+    for c in s.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                pad_backslashes(&mut out, backslashes);
+                backslashes = 0;
+                out.push_str("\\\"");
+                continue;
+            }
+            _ => backslashes = 0,
+        }
+        out.push(c);
+    }
+    pad_backslashes(&mut out, backslashes);
+    out.push('"');
+    Cow::Owned(out)
+}
+
+/// Makes a run of `n` backslashes already written to `out` an even one
+fn pad_backslashes(out: &mut String, n: usize) {
+    if n % 2 == 1 {
+        out.push('\\');
+    }
+}
+
+/// Whether `c` can start an unquoted identifier. Together with [is_id_continue] this is
+/// the same test the parser uses, so anything written bare reads back as a single ID.
+pub(crate) fn is_id_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+pub(crate) fn is_id_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+/// `[a=1, b=2]` with keys in sorted order, or nothing if there are no attributes
+pub(crate) fn attr_list(attrs: &HashMap<String, String>) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    format!(" [{}]", sorted(attrs).join(", "))
+}
+
+/// `key=value` for each attribute, sorted by key
+pub(crate) fn sorted(attrs: &HashMap<String, String>) -> Vec<String> {
+    let mut keys: Vec<&String> = attrs.keys().collect();
+    keys.sort();
+    keys.iter()
+        .map(|k| format!("{}={}", id(k), id(&attrs[*k])))
+        .collect()
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if is_id_start(c) => {}
+        _ => return false,
+    }
+    chars.all(is_id_continue) && !KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(s))
+}
+
+fn is_numeral(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let mut parts = digits.splitn(2, '.');
+    let whole = parts.next().unwrap_or("");
+    let fraction = parts.next();
+    let all_digits = |p: &str| p.chars().all(|c| c.is_ascii_digit());
+    match fraction {
+        None => !whole.is_empty() && all_digits(whole),
+        Some(f) => (!whole.is_empty() || !f.is_empty()) && all_digits(whole) && all_digits(f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_ids_are_left_alone() {
+        for s in ["a", "_b2", "Beta", "1", "-2.5", ".5", "3.", "héllo"].iter() {
+            assert_eq!(id(s), *s);
+        }
+    }
+
+    #[test]
+    fn other_ids_are_quoted() {
+        assert_eq!(id(""), "\"\"");
+        assert_eq!(id("Beta!"), "\"Beta!\"");
+        assert_eq!(id("two words"), "\"two words\"");
+        assert_eq!(id("2b"), "\"2b\"");
+        assert_eq!(id("-"), "\"-\"");
+        assert_eq!(id("1.2.3"), "\"1.2.3\"");
+        assert_eq!(id("node"), "\"node\"");
+        assert_eq!(id("Graph"), "\"Graph\"");
+        assert_eq!(id("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(id("line\\nbreak"), "\"line\\nbreak\"");
+        assert_eq!(id("C:\\"), "\"C:\\\\\"");
+        assert_eq!(id("C:\\\\"), "\"C:\\\\\"");
+        assert_eq!(id("a\\\"b"), "\"a\\\\\\\"b\"");
+        assert_eq!(id("a→b"), "\"a→b\"");
+        assert_eq!(id("x·y"), "\"x·y\"");
+        assert_eq!(id("no\u{a0}break"), "\"no\u{a0}break\"");
+    }
+
+    #[test]
+    fn attributes_are_sorted() {
+        let attrs: HashMap<String, String> = [("z", "1"), ("a", "x y"), ("m", "true")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(attr_list(&attrs), " [a=\"x y\", m=true, z=1]");
+        assert_eq!(attr_list(&HashMap::new()), "");
+    }
+}
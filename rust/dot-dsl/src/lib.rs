@@ -1,33 +1,107 @@
+pub mod algorithms;
+mod dot;
+mod nodes;
+pub mod parse;
+
 pub mod graph {
-    use graph_items::{edge::Edge, node::Node};
+    use crate::nodes::NodeIndex;
+    use graph_items::{edge::Edge, node::Node, subgraph::Subgraph};
     use std::collections::HashMap;
 
+    /// Whether edges are written `a -- b` or `a -> b`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    pub enum GraphKind {
+        #[default]
+        Graph,
+        Digraph,
+    }
+
+    impl GraphKind {
+        fn keyword(self) -> &'static str {
+            match self {
+                GraphKind::Graph => "graph",
+                GraphKind::Digraph => "digraph",
+            }
+        }
+
+        fn edge_op(self) -> &'static str {
+            match self {
+                GraphKind::Graph => "--",
+                GraphKind::Digraph => "->",
+            }
+        }
+    }
+
+    /// Something a graph refers to that isn't in it
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum ValidationError {
+        /// An edge from or to a node the graph doesn't have
+        DanglingEdge {
+            from: String,
+            to: String,
+            missing: String,
+        },
+        /// A subgraph listing a node the graph doesn't have
+        UnknownSubgraphNode {
+            subgraph: Option<String>,
+            node: String,
+        },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
     pub struct Graph {
+        pub kind: GraphKind,
+        /// Strict graphs have at most one edge between any two nodes
+        pub strict: bool,
+        pub name: Option<String>,
+        /// Looked up by name through an index, which copes with the `Vec` being
+        /// changed directly, though [Graph::add_node] is what keeps names unique
         pub nodes: Vec<Node>,
         pub edges: Vec<Edge>,
         pub attrs: HashMap<String, String>,
+        pub subgraphs: Vec<Subgraph>,
+        index: NodeIndex,
     }
 
     impl Graph {
         pub fn new() -> Self {
-            Graph {
-                nodes: Vec::new(),
-                edges: Vec::new(),
-                attrs: HashMap::new(),
-            }
+            Self::default()
         }
 
+        pub fn with_kind(mut self, kind: GraphKind) -> Self {
+            self.kind = kind;
+            self
+        }
+
+        pub fn with_strict(mut self, strict: bool) -> Self {
+            self.strict = strict;
+            self
+        }
+
+        pub fn with_name(mut self, name: &str) -> Self {
+            self.name = Some(name.to_string());
+            self
+        }
+
+        /// Reads a graph written in the DOT language
+        pub fn from_dot(s: &str) -> Result<Self, crate::parse::ParseError> {
+            crate::parse::parse(s)
+        }
+
+        /// Adds nodes in order. A node that is already in the graph gets the new
+        /// node's attributes merged into it.
+        /// O(n + m) for m new nodes
         pub fn with_nodes(mut self, nodes: &[Node]) -> Self {
-            for node in nodes.to_vec() {
-                self.nodes.push(node)
+            self.index.rebuild(&self.nodes);
+            for node in nodes {
+                let at = self.index.trusted(&node.name);
+                self.put_node(node.clone(), at);
             }
             self
         }
 
         pub fn with_edges(mut self, edges: &[Edge]) -> Self {
-            for edge in edges.to_vec() {
-                self.edges.push(edge);
-            }
+            self.edges.extend(edges.iter().cloned());
             self
         }
 
@@ -39,8 +113,162 @@ pub mod graph {
             self
         }
 
+        pub fn with_subgraphs(mut self, subgraphs: &[Subgraph]) -> Self {
+            self.subgraphs.extend(subgraphs.iter().cloned());
+            self
+        }
+
+        /// Every edge end and subgraph member that isn't one of the graph's nodes
+        pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+            let mut errors = Vec::new();
+            for edge in &self.edges {
+                let (from, to) = edge.vertices();
+                for end in [from, to].iter() {
+                    if self.position(end).is_none() {
+                        errors.push(ValidationError::DanglingEdge {
+                            from: from.to_string(),
+                            to: to.to_string(),
+                            missing: end.to_string(),
+                        });
+                    }
+                }
+            }
+
+            let mut subgraphs: Vec<&Subgraph> = self.subgraphs.iter().collect();
+            while let Some(subgraph) = subgraphs.pop() {
+                for node in subgraph.nodes.iter().filter(|n| self.position(n).is_none()) {
+                    errors.push(ValidationError::UnknownSubgraphNode {
+                        subgraph: subgraph.name.clone(),
+                        node: node.clone(),
+                    });
+                }
+                subgraphs.extend(subgraph.subgraphs.iter().rev());
+            }
+
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+
         pub fn get_node(&self, node: &str) -> Option<Node> {
-            self.nodes.iter().find(|&n| n.name == node ).map(|x| x.clone())
+            self.node(node).cloned()
+        }
+
+        /// O(1)
+        pub fn get_node_mut(&mut self, node: &str) -> Option<&mut Node> {
+            let i = self.index.locate(&self.nodes, node)?;
+            Some(&mut self.nodes[i])
+        }
+
+        /// Adds `node`, or merges its attributes into the node of the same name.
+        /// True if the node is new.
+        /// O(1) for a node already there, O(n) to make sure a new one isn't
+        pub fn add_node(&mut self, node: Node) -> bool {
+            let at = self.index.locate(&self.nodes, &node.name);
+            self.put_node(node, at)
+        }
+
+        /// Position of the node called `name` in [nodes](Graph::nodes)
+        /// O(1) for nodes added through the graph's methods
+        pub(crate) fn position(&self, name: &str) -> Option<usize> {
+            self.index.position(&self.nodes, name)
+        }
+
+        /// Merges `node` into the one at `at`, or adds it at the end
+        fn put_node(&mut self, node: Node, at: Option<usize>) -> bool {
+            match at {
+                Some(i) => {
+                    let existing = &mut self.nodes[i];
+                    for (key, value) in node.attrs() {
+                        existing.set_attr(key, value);
+                    }
+                    false
+                }
+                None => {
+                    self.index.insert(&node.name, self.nodes.len());
+                    self.nodes.push(node);
+                    true
+                }
+            }
+        }
+
+        /// Takes out a node along with every edge to or from it, and drops it from
+        /// any subgraph listing it
+        /// O(n + e + s) for s subgraph members
+        pub fn remove_node(&mut self, node: &str) -> Option<Node> {
+            let i = self.index.locate(&self.nodes, node)?;
+            let removed = self.nodes.remove(i);
+            self.index.rebuild(&self.nodes);
+            self.edges.retain(|edge| {
+                let (from, to) = edge.vertices();
+                from != node && to != node
+            });
+            let mut subgraphs: Vec<&mut Subgraph> = self.subgraphs.iter_mut().collect();
+            while let Some(subgraph) = subgraphs.pop() {
+                subgraph.nodes.retain(|n| n != node);
+                subgraphs.extend(subgraph.subgraphs.iter_mut());
+            }
+            Some(removed)
+        }
+
+        /// Adds an edge between two nodes already in the graph. In a strict graph an
+        /// edge that repeats one already there has its attributes merged into it.
+        /// O(1), or O(e) in a strict graph
+        pub fn add_edge(&mut self, edge: Edge) -> Result<(), ValidationError> {
+            let (from, to) = edge.vertices();
+            for end in [from, to] {
+                if self.index.locate(&self.nodes, end).is_none() {
+                    return Err(ValidationError::DanglingEdge {
+                        from: from.to_string(),
+                        to: to.to_string(),
+                        missing: end.to_string(),
+                    });
+                }
+            }
+
+            if self.strict {
+                let existing = self.edges.iter_mut().find(|e| {
+                    let (a, b) = e.vertices();
+                    (a == from && b == to) || (!edge.is_directed() && a == to && b == from)
+                });
+                if let Some(existing) = existing {
+                    for (key, value) in edge.attrs() {
+                        existing.set_attr(key, value);
+                    }
+                    return Ok(());
+                }
+            }
+            self.edges.push(edge);
+            Ok(())
+        }
+
+        /// Writes the graph in the DOT language: graph attributes first, then nodes,
+        /// subgraphs and edges in the order they were added. Attributes are sorted by key.
+        pub fn to_dot(&self) -> String {
+            let mut out = String::new();
+            if self.strict {
+                out += "strict ";
+            }
+            out += self.kind.keyword();
+            if let Some(name) = &self.name {
+                out += &format!(" {}", crate::dot::id(name));
+            }
+            out += " {\n";
+            for attr in crate::dot::sorted(&self.attrs) {
+                out += &format!("    {};\n", attr);
+            }
+            for node in &self.nodes {
+                out += &format!("    {};\n", node.to_dot());
+            }
+            for subgraph in &self.subgraphs {
+                out += &subgraph.to_dot(1);
+            }
+            for edge in &self.edges {
+                out += &format!("    {};\n", edge.to_dot(self.kind));
+            }
+            out + "}\n"
         }
     }
 
@@ -70,29 +298,94 @@ pub mod graph {
                     self
                 }
 
+                pub fn name(&self) -> &str {
+                    &self.name
+                }
+
                 pub fn get_attr(&self, key: &str) -> Option<&str> {
                     self.attrs.get(key).map(|s| &s[..])
                 }
+
+                /// Every attribute, in no particular order
+                pub fn attrs(&self) -> impl Iterator<Item = (&str, &str)> {
+                    self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+                }
+
+                /// Sets an attribute, returning the value it replaced
+                pub fn set_attr(&mut self, key: &str, value: &str) -> Option<String> {
+                    self.attrs.insert(key.to_string(), value.to_string())
+                }
+
+                pub fn remove_attr(&mut self, key: &str) -> Option<String> {
+                    self.attrs.remove(key)
+                }
+
+                /// The node statement, without its `;`
+                pub(crate) fn to_dot(&self) -> String {
+                    format!(
+                        "{}{}",
+                        crate::dot::id(&self.name),
+                        crate::dot::attr_list(&self.attrs)
+                    )
+                }
             }
         }
 
         pub mod edge {
+            use crate::graph::GraphKind;
             use std::collections::HashMap;
 
             #[derive(Clone, Debug, PartialEq, Eq)]
             pub struct Edge {
                 vertices: (String, String),
+                directed: bool,
                 attrs: HashMap<String, String>,
             }
 
             impl Edge {
+                /// An undirected edge
                 pub fn new(v1: &str, v2: &str) -> Self {
                     Edge {
                         vertices: (v1.to_string(), v2.to_string()),
+                        directed: false,
                         attrs: HashMap::new(),
                     }
                 }
 
+                /// An edge that only goes from `from` to `to`
+                pub fn directed(from: &str, to: &str) -> Self {
+                    Edge {
+                        directed: true,
+                        ..Edge::new(from, to)
+                    }
+                }
+
+                pub fn is_directed(&self) -> bool {
+                    self.directed
+                }
+
+                pub fn vertices(&self) -> (&str, &str) {
+                    (&self.vertices.0, &self.vertices.1)
+                }
+
+                pub fn get_attr(&self, key: &str) -> Option<&str> {
+                    self.attrs.get(key).map(|s| &s[..])
+                }
+
+                /// Every attribute, in no particular order
+                pub fn attrs(&self) -> impl Iterator<Item = (&str, &str)> {
+                    self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+                }
+
+                /// Sets an attribute, returning the value it replaced
+                pub fn set_attr(&mut self, key: &str, value: &str) -> Option<String> {
+                    self.attrs.insert(key.to_string(), value.to_string())
+                }
+
+                pub fn remove_attr(&mut self, key: &str) -> Option<String> {
+                    self.attrs.remove(key)
+                }
+
                 pub fn with_attrs(mut self, attrs: &[(&str, &str)]) -> Self {
                     attrs.to_vec().iter().fold(&mut self.attrs, |acc, (k, v)| {
                         acc.insert(k.to_string(), v.to_string());
@@ -100,6 +393,105 @@ pub mod graph {
                     });
                     self
                 }
+
+                /// The edge statement, without its `;`. An edge that doesn't match the
+                /// graph's kind gets a `dir` attribute to say which way it goes.
+                pub(crate) fn to_dot(&self, kind: GraphKind) -> String {
+                    let mut attrs = self.attrs.clone();
+                    match (kind, self.directed) {
+                        (GraphKind::Graph, true) => {
+                            attrs.insert("dir".to_string(), "forward".to_string());
+                        }
+                        (GraphKind::Digraph, false) => {
+                            attrs.insert("dir".to_string(), "none".to_string());
+                        }
+                        _ => {}
+                    }
+                    format!(
+                        "{} {} {}{}",
+                        crate::dot::id(&self.vertices.0),
+                        kind.edge_op(),
+                        crate::dot::id(&self.vertices.1),
+                        crate::dot::attr_list(&attrs)
+                    )
+                }
+            }
+        }
+
+        pub mod subgraph {
+            use std::collections::HashMap;
+
+            /// A named or anonymous group of the graph's nodes, which may hold further
+            /// subgraphs. Subgraphs whose name starts with `cluster` are drawn in a box.
+            #[derive(Clone, Debug, PartialEq, Eq, Default)]
+            pub struct Subgraph {
+                pub name: Option<String>,
+                /// Names of the nodes directly in this subgraph, not in its subgraphs
+                pub nodes: Vec<String>,
+                pub attrs: HashMap<String, String>,
+                pub subgraphs: Vec<Subgraph>,
+            }
+
+            impl Subgraph {
+                pub fn new(name: &str) -> Self {
+                    Subgraph {
+                        name: Some(name.to_string()),
+                        ..Subgraph::default()
+                    }
+                }
+
+                pub fn anonymous() -> Self {
+                    Subgraph::default()
+                }
+
+                /// A subgraph named `cluster_` followed by `name`
+                pub fn cluster(name: &str) -> Self {
+                    Subgraph::new(&format!("cluster_{}", name))
+                }
+
+                pub fn is_cluster(&self) -> bool {
+                    self.name
+                        .as_ref()
+                        .is_some_and(|name| name.starts_with("cluster"))
+                }
+
+                pub fn with_nodes(mut self, nodes: &[&str]) -> Self {
+                    self.nodes.extend(nodes.iter().map(|n| n.to_string()));
+                    self
+                }
+
+                pub fn with_attrs(mut self, attrs: &[(&str, &str)]) -> Self {
+                    attrs.to_vec().iter().fold(&mut self.attrs, |acc, (k, v)| {
+                        acc.insert(k.to_string(), v.to_string());
+                        acc
+                    });
+                    self
+                }
+
+                pub fn with_subgraphs(mut self, subgraphs: &[Subgraph]) -> Self {
+                    self.subgraphs.extend(subgraphs.iter().cloned());
+                    self
+                }
+
+                /// The whole subgraph block, one statement per line, `depth` levels in
+                pub(crate) fn to_dot(&self, depth: usize) -> String {
+                    let indent = "    ".repeat(depth);
+                    let mut out = format!("{}subgraph ", indent);
+                    if let Some(name) = &self.name {
+                        out += &format!("{} ", crate::dot::id(name));
+                    }
+                    out += "{\n";
+                    for attr in crate::dot::sorted(&self.attrs) {
+                        out += &format!("{}    {};\n", indent, attr);
+                    }
+                    for node in &self.nodes {
+                        out += &format!("{}    {};\n", indent, crate::dot::id(node));
+                    }
+                    for subgraph in &self.subgraphs {
+                        out += &subgraph.to_dot(depth + 1);
+                    }
+                    out + &indent + "}\n"
+                }
             }
         }
     }
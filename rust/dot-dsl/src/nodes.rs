@@ -0,0 +1,111 @@
+//! An index from node name to position, kept beside a graph's `Vec<Node>`.
+
+use crate::graph::graph_items::node::Node;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Where each node sits in [Graph::nodes](crate::graph::Graph::nodes). The graph's own
+/// methods keep it up to date, but the `Vec` is public and can be changed behind its back,
+/// so every hit is checked against the node it points at and a miss is confirmed with
+/// a scan. Nodes added or renamed directly are still found, just not in O(1) until the
+/// index is repaired by a method that takes `&mut Graph`.
+#[derive(Clone, Default)]
+pub(crate) struct NodeIndex {
+    positions: HashMap<String, usize>,
+}
+
+impl NodeIndex {
+    /// Position of the node called `name`
+    /// O(1) if the index knows about it, O(n) otherwise
+    pub(crate) fn position(&self, nodes: &[Node], name: &str) -> Option<usize> {
+        match self.positions.get(name) {
+            Some(&i) if nodes.get(i).is_some_and(|n| n.name == name) => Some(i),
+            _ => nodes.iter().position(|n| n.name == name),
+        }
+    }
+
+    /// Like [position](NodeIndex::position), but records what a scan finds
+    pub(crate) fn locate(&mut self, nodes: &[Node], name: &str) -> Option<usize> {
+        let found = self.position(nodes, name);
+        match found {
+            Some(i) => self.positions.insert(name.to_string(), i),
+            None => self.positions.remove(name),
+        };
+        found
+    }
+
+    /// Position of the node called `name`, trusting the index. Only right after a
+    /// [rebuild](NodeIndex::rebuild), with every change since recorded.
+    /// O(1)
+    pub(crate) fn trusted(&self, name: &str) -> Option<usize> {
+        self.positions.get(name).copied()
+    }
+
+    pub(crate) fn insert(&mut self, name: &str, position: usize) {
+        self.positions.insert(name.to_string(), position);
+    }
+
+    /// Indexes every node from scratch, keeping the first of any repeated name
+    /// O(n)
+    pub(crate) fn rebuild(&mut self, nodes: &[Node]) {
+        self.positions.clear();
+        for (i, node) in nodes.iter().enumerate() {
+            self.positions.entry(node.name.clone()).or_insert(i);
+        }
+    }
+}
+
+/// The index is derived from the nodes, so it never makes two graphs different
+impl PartialEq for NodeIndex {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for NodeIndex {}
+
+impl fmt::Debug for NodeIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NodeIndex").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(names: &[&str]) -> Vec<Node> {
+        names.iter().map(|n| Node::new(n)).collect()
+    }
+
+    #[test]
+    fn rebuilt_index_finds_first_of_each_name() {
+        let nodes = nodes(&["a", "b", "a"]);
+        let mut index = NodeIndex::default();
+        index.rebuild(&nodes);
+        assert_eq!(index.trusted("a"), Some(0));
+        assert_eq!(index.trusted("b"), Some(1));
+        assert_eq!(index.trusted("c"), None);
+    }
+
+    #[test]
+    fn stale_entries_are_caught_and_repaired() {
+        let mut nodes = nodes(&["a", "b", "c"]);
+        let mut index = NodeIndex::default();
+        index.rebuild(&nodes);
+
+        nodes.remove(0);
+        nodes.push(Node::new("d"));
+        nodes[0].name = "e".to_string();
+        assert_eq!(index.position(&nodes, "a"), None);
+        assert_eq!(index.position(&nodes, "b"), None);
+        assert_eq!(index.position(&nodes, "c"), Some(1));
+        assert_eq!(index.position(&nodes, "d"), Some(2));
+        assert_eq!(index.position(&nodes, "e"), Some(0));
+
+        assert_eq!(index.locate(&nodes, "e"), Some(0));
+        assert_eq!(index.trusted("e"), Some(0));
+        assert_eq!(index.locate(&nodes, "a"), None);
+        assert_eq!(index.trusted("a"), None);
+    }
+}
@@ -0,0 +1,828 @@
+//! Reading graphs written in the DOT language.
+//!
+//! Nodes are created the first time they are mentioned, in a node statement or an edge,
+//! and `node [...]` and `edge [...]` defaults are applied to the nodes and edges that
+//! come after them, until the end of the subgraph they are in. Edges follow the graph's
+//! kind unless a `dir` attribute says otherwise. Ports and HTML strings aren't supported yet.
+
+use crate::dot::{is_id_continue, is_id_start};
+use crate::graph::graph_items::{edge::Edge, node::Node, subgraph::Subgraph};
+use crate::graph::{Graph, GraphKind};
+use std::collections::HashMap;
+use std::fmt;
+
+/// How deeply subgraphs can nest, so that deep input can't overflow the stack
+pub const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedChar(char),
+    UnterminatedString,
+    UnterminatedComment,
+    /// Something other than what the grammar allows here, described by `found`
+    Expected {
+        expected: &'static str,
+        found: String,
+    },
+    Unsupported(&'static str),
+    /// Subgraphs nested more than [MAX_DEPTH] deep
+    TooDeep,
+}
+
+/// Where parsing stopped, with lines and columns counted from 1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ParseErrorKind::UnterminatedComment => write!(f, "unterminated comment"),
+            ParseErrorKind::Expected { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            ParseErrorKind::Unsupported(what) => write!(f, "{} are not supported", what),
+            ParseErrorKind::TooDeep => {
+                write!(f, "subgraphs nested more than {} deep", MAX_DEPTH)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An identifier, number or quoted string. Only unquoted IDs can be keywords.
+    Id {
+        text: String,
+        quoted: bool,
+    },
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semicolon,
+    Comma,
+    Equals,
+    Colon,
+    EdgeOp(GraphKind),
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Id { text, .. } => format!("`{}`", text),
+            Token::LBrace => "`{`".to_string(),
+            Token::RBrace => "`}`".to_string(),
+            Token::LBracket => "`[`".to_string(),
+            Token::RBracket => "`]`".to_string(),
+            Token::Semicolon => "`;`".to_string(),
+            Token::Comma => "`,`".to_string(),
+            Token::Equals => "`=`".to_string(),
+            Token::Colon => "`:`".to_string(),
+            Token::EdgeOp(GraphKind::Graph) => "`--`".to_string(),
+            Token::EdgeOp(GraphKind::Digraph) => "`->`".to_string(),
+            Token::Eof => "end of input".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self {
+            Token::Id {
+                text,
+                quoted: false,
+            } => text.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// Nothing but whitespace so far on this line, so `#` starts a comment
+    line_start: bool,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(s: &'a str) -> Self {
+        Lexer {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+            line_start: true,
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.line_start = true;
+        } else {
+            self.column += 1;
+            if !c.is_whitespace() {
+                self.line_start = false;
+            }
+        }
+        Some(c)
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut ahead = self.chars.clone();
+        ahead.next();
+        ahead.next()
+    }
+
+    /// Skips whitespace and comments
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' && self.line_start {
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.bump();
+                }
+            } else if c == '/' && self.peek_second() == Some('/') {
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.bump();
+                }
+            } else if c == '/' && self.peek_second() == Some('*') {
+                let start = self.error(ParseErrorKind::UnterminatedComment);
+                self.bump();
+                self.bump();
+                loop {
+                    match self.bump() {
+                        Some('*') if self.chars.peek() == Some(&'/') => {
+                            self.bump();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => return Err(start),
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// The next token and where it starts
+    fn next_token(&mut self) -> Result<(Token, usize, usize), ParseError> {
+        self.skip_trivia()?;
+        let (line, column) = (self.line, self.column);
+        let c = match self.chars.peek() {
+            Some(&c) => c,
+            None => return Ok((Token::Eof, line, column)),
+        };
+
+        let single = match c {
+            '{' => Some(Token::LBrace),
+            '}' => Some(Token::RBrace),
+            '[' => Some(Token::LBracket),
+            ']' => Some(Token::RBracket),
+            ';' => Some(Token::Semicolon),
+            ',' => Some(Token::Comma),
+            '=' => Some(Token::Equals),
+            ':' => Some(Token::Colon),
+            _ => None,
+        };
+        if let Some(token) = single {
+            self.bump();
+            return Ok((token, line, column));
+        }
+
+        let token = match c {
+            '-' if self.peek_second() == Some('-') => {
+                self.bump();
+                self.bump();
+                Token::EdgeOp(GraphKind::Graph)
+            }
+            '-' if self.peek_second() == Some('>') => {
+                self.bump();
+                self.bump();
+                Token::EdgeOp(GraphKind::Digraph)
+            }
+            '"' => self.quoted()?,
+            '<' => return Err(self.error(ParseErrorKind::Unsupported("HTML strings"))),
+            c if c == '-' || c == '.' || c.is_ascii_digit() => self.numeral(),
+            c if is_id_start(c) => {
+                let mut text = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if is_id_continue(c) {
+                        text.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Id {
+                    text,
+                    quoted: false,
+                }
+            }
+            c => return Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+        };
+        Ok((token, line, column))
+    }
+
+    fn numeral(&mut self) -> Token {
+        let mut text = String::new();
+        if self.chars.peek() == Some(&'-') {
+            text.push('-');
+            self.bump();
+        }
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_digit() || (c == '.' && !text.contains('.')) {
+                text.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        Token::Id {
+            text,
+            quoted: false,
+        }
+    }
+
+    /// A quoted string, joined to any that follow it with `+`
+    fn quoted(&mut self) -> Result<Token, ParseError> {
+        let mut text = String::new();
+        loop {
+            let start = self.error(ParseErrorKind::UnterminatedString);
+            self.bump();
+            loop {
+                match self.bump() {
+                    Some('"') => break,
+                    Some('\\') => match self.chars.peek() {
+                        Some('"') => {
+                            text.push('"');
+                            self.bump();
+                        }
+                        // kept as a pair, so the second one can't escape a quote
+                        Some('\\') => {
+                            text.push_str("\\\\");
+                            self.bump();
+                        }
+                        Some('\n') => {
+                            self.bump();
+                        }
+                        _ => text.push('\\'),
+                    },
+                    Some(c) => text.push(c),
+                    None => return Err(start),
+                }
+            }
+
+            // Look past trivia for `+ "..."` without consuming anything else
+            let mut ahead = Lexer {
+                chars: self.chars.clone(),
+                ..*self
+            };
+            ahead.skip_trivia()?;
+            if ahead.chars.peek() != Some(&'+') {
+                break;
+            }
+            ahead.bump();
+            ahead.skip_trivia()?;
+            match ahead.chars.peek() {
+                Some('"') => {}
+                next => {
+                    let found = next.map_or("end of input".to_string(), |c| format!("`{}`", c));
+                    return Err(ahead.error(ParseErrorKind::Expected {
+                        expected: "a quoted string after `+`",
+                        found,
+                    }));
+                }
+            }
+            *self = ahead;
+        }
+        Ok(Token::Id { text, quoted: true })
+    }
+}
+
+/// A parsed node or edge, before it is turned into a [Node] or [Edge]
+type Attrs = Vec<(String, String)>;
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    token: Token,
+    line: usize,
+    column: usize,
+    kind: GraphKind,
+    strict: bool,
+    node_defaults: Attrs,
+    edge_defaults: Attrs,
+    graph_attrs: Attrs,
+    nodes: Vec<(String, Attrs)>,
+    node_index: HashMap<String, usize>,
+    edges: Vec<(String, String, Attrs)>,
+    /// Finished top level subgraphs
+    subgraphs: Vec<Subgraph>,
+    /// Subgraphs being parsed, innermost last
+    open: Vec<Subgraph>,
+}
+
+impl<'a> Parser<'a> {
+    fn advance(&mut self) -> Result<Token, ParseError> {
+        let (next, line, column) = self.lexer.next_token()?;
+        self.line = line;
+        self.column = column;
+        Ok(std::mem::replace(&mut self.token, next))
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    fn expected(&self, expected: &'static str) -> ParseError {
+        self.error(ParseErrorKind::Expected {
+            expected,
+            found: self.token.describe(),
+        })
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), ParseError> {
+        if self.token != token {
+            return Err(self.expected(expected));
+        }
+        self.advance()?;
+        Ok(())
+    }
+
+    fn id(&mut self, expected: &'static str) -> Result<String, ParseError> {
+        match &self.token {
+            Token::Id { .. } if !self.is_keyword() => match self.advance()? {
+                Token::Id { text, .. } => Ok(text),
+                _ => unreachable!(),
+            },
+            _ => Err(self.expected(expected)),
+        }
+    }
+
+    fn is_keyword(&self) -> bool {
+        ["node", "edge", "graph", "digraph", "subgraph", "strict"]
+            .iter()
+            .any(|k| self.token.is_keyword(k))
+    }
+
+    fn graph(&mut self) -> Result<Graph, ParseError> {
+        if self.token.is_keyword("strict") {
+            self.strict = true;
+            self.advance()?;
+        }
+        self.kind = if self.token.is_keyword("graph") {
+            GraphKind::Graph
+        } else if self.token.is_keyword("digraph") {
+            GraphKind::Digraph
+        } else {
+            return Err(self.expected("`graph` or `digraph`"));
+        };
+        self.advance()?;
+
+        let name = match self.token {
+            Token::Id { .. } => Some(self.id("a graph name")?),
+            _ => None,
+        };
+        self.expect(Token::LBrace, "`{`")?;
+        while self.token != Token::RBrace {
+            self.statement()?;
+            if self.token == Token::Semicolon {
+                self.advance()?;
+            }
+        }
+        self.advance()?;
+        if self.token != Token::Eof {
+            return Err(self.expected("end of input"));
+        }
+
+        let nodes: Vec<Node> = self
+            .nodes
+            .iter()
+            .map(|(name, attrs)| Node::new(name).with_attrs(&borrowed(attrs)))
+            .collect();
+        let mut graph = Graph::new()
+            .with_kind(self.kind)
+            .with_strict(self.strict)
+            .with_attrs(&borrowed(&self.graph_attrs))
+            .with_nodes(&nodes);
+        graph.name = name;
+        let (flipped, directed) = match self.kind {
+            GraphKind::Graph => ("forward", false),
+            GraphKind::Digraph => ("none", true),
+        };
+        graph.edges = self
+            .edges
+            .iter_mut()
+            .map(|(a, b, attrs)| {
+                let before = attrs.len();
+                attrs.retain(|(k, v)| !(k == "dir" && v == flipped));
+                let edge = if directed == (attrs.len() == before) {
+                    Edge::directed(a, b)
+                } else {
+                    Edge::new(a, b)
+                };
+                edge.with_attrs(&borrowed(attrs))
+            })
+            .collect();
+        graph.subgraphs = std::mem::take(&mut self.subgraphs);
+        Ok(graph)
+    }
+
+    fn statement(&mut self) -> Result<(), ParseError> {
+        if self.token.is_keyword("graph") {
+            self.advance()?;
+            let attrs = self.attr_lists()?;
+            self.graph_attrs(attrs);
+            return Ok(());
+        }
+        if self.token.is_keyword("node") {
+            self.advance()?;
+            let attrs = self.attr_lists()?;
+            merge(&mut self.node_defaults, attrs);
+            return Ok(());
+        }
+        if self.token.is_keyword("edge") {
+            self.advance()?;
+            let attrs = self.attr_lists()?;
+            merge(&mut self.edge_defaults, attrs);
+            return Ok(());
+        }
+
+        let first = if self.at_subgraph() {
+            self.subgraph()?
+        } else {
+            let id = self.id("a statement")?;
+            if self.token == Token::Equals {
+                self.advance()?;
+                let value = self.id("an attribute value")?;
+                self.graph_attrs(vec![(id, value)]);
+                return Ok(());
+            }
+            self.no_port()?;
+            if !matches!(self.token, Token::EdgeOp(_)) {
+                let attrs = if self.token == Token::LBracket {
+                    self.attr_lists()?
+                } else {
+                    Vec::new()
+                };
+                let node = self.node(id);
+                merge(&mut self.nodes[node].1, attrs);
+                return Ok(());
+            }
+            self.node(id.clone());
+            vec![id]
+        };
+
+        // Each end of an edge is a node or every node in a subgraph
+        let mut chain = vec![first];
+        while let Token::EdgeOp(kind) = self.token {
+            if kind != self.kind {
+                return Err(self.expected(match self.kind {
+                    GraphKind::Graph => "`--` in a graph",
+                    GraphKind::Digraph => "`->` in a digraph",
+                }));
+            }
+            self.advance()?;
+            let end = if self.at_subgraph() {
+                self.subgraph()?
+            } else {
+                let id = self.id("a node or subgraph")?;
+                self.no_port()?;
+                self.node(id.clone());
+                vec![id]
+            };
+            chain.push(end);
+        }
+        if chain.len() == 1 {
+            return Ok(());
+        }
+
+        let attrs = if self.token == Token::LBracket {
+            self.attr_lists()?
+        } else {
+            Vec::new()
+        };
+        for pair in chain.windows(2) {
+            for a in &pair[0] {
+                for b in &pair[1] {
+                    self.edge(a, b, attrs.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn at_subgraph(&self) -> bool {
+        self.token.is_keyword("subgraph") || self.token == Token::LBrace
+    }
+
+    /// Parses a subgraph and returns the names of every node in it
+    fn subgraph(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut subgraph = Subgraph::anonymous();
+        if self.token.is_keyword("subgraph") {
+            self.advance()?;
+            if matches!(self.token, Token::Id { .. }) {
+                subgraph.name = Some(self.id("a subgraph name")?);
+            }
+        }
+        if self.token == Token::LBrace && self.open.len() == MAX_DEPTH {
+            return Err(self.error(ParseErrorKind::TooDeep));
+        }
+        self.expect(Token::LBrace, "`{`")?;
+
+        let defaults = (self.node_defaults.clone(), self.edge_defaults.clone());
+        self.open.push(subgraph);
+        while self.token != Token::RBrace {
+            self.statement()?;
+            if self.token == Token::Semicolon {
+                self.advance()?;
+            }
+        }
+        self.advance()?;
+        let subgraph = self.open.pop().unwrap();
+        let (nodes, edges) = defaults;
+        self.node_defaults = nodes;
+        self.edge_defaults = edges;
+
+        let mut names: Vec<String> = Vec::new();
+        let mut pending = vec![&subgraph];
+        while let Some(s) = pending.pop() {
+            for node in &s.nodes {
+                if !names.contains(node) {
+                    names.push(node.clone());
+                }
+            }
+            pending.extend(s.subgraphs.iter().rev());
+        }
+        match self.open.last_mut() {
+            Some(parent) => parent.subgraphs.push(subgraph),
+            None => self.subgraphs.push(subgraph),
+        }
+        Ok(names)
+    }
+
+    /// Sets attributes on the innermost open subgraph, or the graph itself
+    fn graph_attrs(&mut self, attrs: Attrs) {
+        match self.open.last_mut() {
+            Some(subgraph) => subgraph.attrs.extend(attrs),
+            None => merge(&mut self.graph_attrs, attrs),
+        }
+    }
+
+    fn no_port(&self) -> Result<(), ParseError> {
+        if self.token == Token::Colon {
+            return Err(self.error(ParseErrorKind::Unsupported("ports")));
+        }
+        Ok(())
+    }
+
+    /// The index of the named node, created with the current defaults if it is new.
+    /// It also joins the innermost open subgraph.
+    fn node(&mut self, name: String) -> usize {
+        if let Some(subgraph) = self.open.last_mut() {
+            if !subgraph.nodes.contains(&name) {
+                subgraph.nodes.push(name.clone());
+            }
+        }
+        if let Some(&i) = self.node_index.get(&name) {
+            return i;
+        }
+        self.node_index.insert(name.clone(), self.nodes.len());
+        self.nodes.push((name, self.node_defaults.clone()));
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge, or in a strict graph merges it into the edge it repeats
+    fn edge(&mut self, a: &str, b: &str, attrs: Attrs) {
+        if self.strict {
+            let undirected = self.kind == GraphKind::Graph;
+            let existing = self
+                .edges
+                .iter_mut()
+                .find(|(x, y, _)| (x == a && y == b) || (undirected && x == b && y == a));
+            if let Some((_, _, existing)) = existing {
+                merge(existing, attrs);
+                return;
+            }
+        }
+        let mut all = self.edge_defaults.clone();
+        merge(&mut all, attrs);
+        self.edges.push((a.to_string(), b.to_string(), all));
+    }
+
+    /// One or more `[a=1, b=2]` lists
+    fn attr_lists(&mut self) -> Result<Attrs, ParseError> {
+        let mut attrs = Vec::new();
+        self.expect(Token::LBracket, "`[`")?;
+        loop {
+            while self.token != Token::RBracket {
+                let key = self.id("an attribute name or `]`")?;
+                self.expect(Token::Equals, "`=`")?;
+                let value = self.id("an attribute value")?;
+                merge(&mut attrs, vec![(key, value)]);
+                if self.token == Token::Comma || self.token == Token::Semicolon {
+                    self.advance()?;
+                }
+            }
+            self.advance()?;
+            if self.token != Token::LBracket {
+                return Ok(attrs);
+            }
+            self.advance()?;
+        }
+    }
+}
+
+fn borrowed(attrs: &Attrs) -> Vec<(&str, &str)> {
+    attrs
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect()
+}
+
+/// Sets each of `new` in `attrs`, replacing any earlier value for the same key
+fn merge(attrs: &mut Attrs, new: Attrs) {
+    for (key, value) in new {
+        match attrs.iter_mut().find(|(k, _)| *k == key) {
+            Some(existing) => existing.1 = value,
+            None => attrs.push((key, value)),
+        }
+    }
+}
+
+/// Parses a whole DOT file into a [Graph]
+pub fn parse(s: &str) -> Result<Graph, ParseError> {
+    let mut lexer = Lexer::new(s);
+    let (token, line, column) = lexer.next_token()?;
+    Parser {
+        lexer,
+        token,
+        line,
+        column,
+        kind: GraphKind::Graph,
+        strict: false,
+        node_defaults: Vec::new(),
+        edge_defaults: Vec::new(),
+        graph_attrs: Vec::new(),
+        nodes: Vec::new(),
+        node_index: HashMap::new(),
+        edges: Vec::new(),
+        subgraphs: Vec::new(),
+        open: Vec::new(),
+    }
+    .graph()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(s: &str) -> (usize, usize, ParseErrorKind) {
+        let e = parse(s).unwrap_err();
+        (e.line, e.column, e.kind)
+    }
+
+    #[test]
+    fn comments_and_whitespace_are_skipped() {
+        let g = parse("# generated\n// a comment\ngraph /* in\nline */ {\n  # too\n  a\n}\n");
+        assert_eq!(g.unwrap().nodes, vec![Node::new("a")]);
+        assert_eq!(
+            error("graph { a # b }"),
+            (1, 11, ParseErrorKind::UnexpectedChar('#'))
+        );
+    }
+
+    #[test]
+    fn ids_can_be_quoted_numbers_or_joined() {
+        let g =
+            parse(r#"graph { "a b" -- -1.5 -- .5; "say \"hi\"" + " there" [x="\N"] }"#).unwrap();
+        let names: Vec<_> = g.nodes.iter().map(|n| n.name()).collect();
+        assert_eq!(names, vec!["a b", "-1.5", ".5", "say \"hi\" there"]);
+        assert_eq!(g.nodes[3].get_attr("x"), Some("\\N"));
+        assert_eq!(g.edges.len(), 2);
+
+        let g = parse(r#"graph { "C:\\" -- "\N" }"#).unwrap();
+        let names: Vec<_> = g.nodes.iter().map(|n| n.name()).collect();
+        assert_eq!(names, vec!["C:\\\\", "\\N"]);
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive_unless_quoted() {
+        let g = parse(r#"DiGraph G { NODE [shape=box]; "node" }"#).unwrap();
+        assert_eq!(g.kind, GraphKind::Digraph);
+        assert_eq!(g.name.as_deref(), Some("G"));
+        assert_eq!(
+            g.nodes,
+            vec![Node::new("node").with_attrs(&[("shape", "box")])]
+        );
+    }
+
+    #[test]
+    fn errors_have_lines_and_columns() {
+        assert_eq!(
+            error("graph {\n  a -> b\n}"),
+            (
+                2,
+                5,
+                ParseErrorKind::Expected {
+                    expected: "`--` in a graph",
+                    found: "`->`".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            error("digraph {\n  a [color=]\n}"),
+            (
+                2,
+                12,
+                ParseErrorKind::Expected {
+                    expected: "an attribute value",
+                    found: "`]`".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            error("graph { a }\n}"),
+            (
+                2,
+                1,
+                ParseErrorKind::Expected {
+                    expected: "end of input",
+                    found: "`}`".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            error("graph {\n \"abc"),
+            (2, 2, ParseErrorKind::UnterminatedString)
+        );
+        assert_eq!(
+            error("graph { /* "),
+            (1, 9, ParseErrorKind::UnterminatedComment)
+        );
+        assert_eq!(
+            error("graph { a ! }"),
+            (1, 11, ParseErrorKind::UnexpectedChar('!'))
+        );
+        assert_eq!(
+            error("graph { a:n -- b }"),
+            (1, 10, ParseErrorKind::Unsupported("ports"))
+        );
+        assert_eq!(
+            error("graph { a [label=<b>] }"),
+            (1, 18, ParseErrorKind::Unsupported("HTML strings"))
+        );
+        let deep = format!("graph {}", "{".repeat(1_000_000));
+        assert_eq!(error(&deep), (1, 8 + MAX_DEPTH, ParseErrorKind::TooDeep));
+        let nested = format!(
+            "graph {{ {}a{} }}",
+            "{".repeat(MAX_DEPTH),
+            "}".repeat(MAX_DEPTH)
+        );
+        assert!(parse(&nested).is_ok());
+        assert_eq!(
+            error("node { }"),
+            (
+                1,
+                1,
+                ParseErrorKind::Expected {
+                    expected: "`graph` or `digraph`",
+                    found: "`node`".to_string()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn errors_display_their_position() {
+        assert_eq!(
+            parse("graph {").unwrap_err().to_string(),
+            "1:8: expected a statement, found end of input"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use dot_dsl::graph::graph_items::edge::Edge;
 use dot_dsl::graph::graph_items::node::Node;
-use dot_dsl::graph::Graph;
+use dot_dsl::graph::graph_items::subgraph::Subgraph;
+use dot_dsl::graph::{Graph, GraphKind, ValidationError};
 use maplit::hashmap;
 
 #[test]
@@ -118,6 +119,7 @@ fn test_graph_with_attributes() {
 }
 
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_graph_stores_attributes() {
     let attributes = [("foo", "bar"), ("bat", "baz"), ("bim", "bef")];
     let graph = Graph::new().with_nodes(
@@ -136,3 +138,452 @@ fn test_graph_stores_attributes() {
         Some("bef")
     );
 }
+
+#[test]
+fn test_empty_graph_to_dot() {
+    assert_eq!(Graph::new().to_dot(), "graph {\n}\n");
+}
+
+#[test]
+fn test_graph_to_dot() {
+    let graph = Graph::new()
+        .with_nodes(&[
+            Node::new("a").with_attrs(&[("color", "green")]),
+            Node::new("c"),
+            Node::new("b").with_attrs(&[("label", "Beta!"), ("shape", "box")]),
+        ])
+        .with_edges(&[
+            Edge::new("b", "c"),
+            Edge::new("a", "b").with_attrs(&[("color", "blue")]),
+        ])
+        .with_attrs(&[("foo", "1"), ("title", "Testing Attrs"), ("bar", "true")]);
+
+    assert_eq!(
+        graph.to_dot(),
+        r#"graph {
+    bar=true;
+    foo=1;
+    title="Testing Attrs";
+    a [color=green];
+    c;
+    b [label="Beta!", shape=box];
+    b -- c;
+    a -- b [color=blue];
+}
+"#
+    );
+}
+
+#[test]
+fn test_digraph_to_dot_quotes_ids() {
+    let graph = Graph::new()
+        .with_kind(GraphKind::Digraph)
+        .with_nodes(&[Node::new("web server").with_attrs(&[("label", "say \"hi\"")])])
+        .with_edges(&[Edge::directed("web server", "node")]);
+
+    assert_eq!(
+        graph.to_dot(),
+        r#"digraph {
+    "web server" [label="say \"hi\""];
+    "web server" -> "node";
+}
+"#
+    );
+}
+
+#[test]
+fn test_parse_graph() {
+    let graph = Graph::from_dot(
+        r#"
+        /* services */
+        strict digraph "my services" {
+            rankdir=LR
+            graph [fontsize=10];
+            node [shape=box]
+            web [label="Web\nfront"];
+            edge [color=gray]
+            web -> api -> db [weight=2];
+            web -> api [color=red]
+            node [shape=ellipse]
+            cache
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(graph.kind, GraphKind::Digraph);
+    assert!(graph.strict);
+    assert_eq!(graph.name.as_deref(), Some("my services"));
+    assert_eq!(
+        graph.attrs,
+        hashmap! {
+            "rankdir".to_string() => "LR".to_string(),
+            "fontsize".to_string() => "10".to_string(),
+        }
+    );
+    assert_eq!(
+        graph.nodes,
+        vec![
+            Node::new("web").with_attrs(&[("shape", "box"), ("label", "Web\\nfront")]),
+            Node::new("api").with_attrs(&[("shape", "box")]),
+            Node::new("db").with_attrs(&[("shape", "box")]),
+            Node::new("cache").with_attrs(&[("shape", "ellipse")]),
+        ]
+    );
+    assert_eq!(
+        graph.edges,
+        vec![
+            Edge::directed("web", "api").with_attrs(&[("color", "red"), ("weight", "2")]),
+            Edge::directed("api", "db").with_attrs(&[("color", "gray"), ("weight", "2")]),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_then_print_round_trips() {
+    let graph = Graph::new()
+        .with_kind(GraphKind::Digraph)
+        .with_strict(true)
+        .with_name("G 1")
+        .with_nodes(&[
+            Node::new("a").with_attrs(&[("label", "say \"hi\""), ("width", "-1.5")]),
+            Node::new("node").with_attrs(&[("label", "a\\lb")]),
+            Node::new("c"),
+        ])
+        .with_edges(&[
+            Edge::directed("a", "node").with_attrs(&[("color", "blue")]),
+            Edge::new("node", "c"),
+        ])
+        .with_attrs(&[("title", "Testing Attrs")])
+        .with_subgraphs(&[Subgraph::cluster("api")
+            .with_nodes(&["a"])
+            .with_attrs(&[("label", "API")])
+            .with_subgraphs(&[Subgraph::anonymous().with_nodes(&["node"])])]);
+
+    let dot = graph.to_dot();
+    let parsed = Graph::from_dot(&dot).unwrap();
+    assert_eq!(parsed, graph);
+    assert_eq!(parsed.to_dot(), dot);
+}
+
+#[test]
+fn test_awkward_names_round_trip() {
+    let names = [
+        "a→b",
+        "x·y",
+        "no\u{a0}break",
+        "C:\\\\",
+        "ends in \\\\\"",
+        "héllo",
+        "2b",
+        "-",
+        "Strict",
+    ];
+    let graph = Graph::new()
+        .with_nodes(&names.iter().map(|&n| Node::new(n)).collect::<Vec<_>>())
+        .with_edges(&[Edge::new("a→b", "C:\\\\").with_attrs(&[("label", "x·y\\n")])]);
+
+    let dot = graph.to_dot();
+    let parsed = Graph::from_dot(&dot).unwrap_or_else(|e| panic!("{}\n{}", e, dot));
+    assert_eq!(parsed, graph);
+    assert_eq!(parsed.to_dot(), dot);
+}
+
+#[test]
+fn test_label_escapes_are_written_as_they_are() {
+    let graph = Graph::new()
+        .with_nodes(&[Node::new("a").with_attrs(&[("label", r"one\ntwo\l"), ("xlabel", r"\N\r")])]);
+    let dot = graph.to_dot();
+    assert_eq!(
+        dot,
+        "graph {\n    a [label=\"one\\ntwo\\l\", xlabel=\"\\N\\r\"];\n}\n"
+    );
+    assert_eq!(Graph::from_dot(&dot).unwrap(), graph);
+
+    // a lone backslash at the end is doubled so it can't escape the closing quote,
+    // and reads back as DOT's escaped backslash
+    let graph = Graph::new().with_nodes(&[Node::new("C:\\")]);
+    assert_eq!(graph.to_dot(), "graph {\n    \"C:\\\\\";\n}\n");
+    let parsed = Graph::from_dot(&graph.to_dot()).unwrap();
+    assert_eq!(parsed.nodes[0].name, "C:\\\\");
+    assert_eq!(parsed.to_dot(), graph.to_dot());
+}
+
+#[test]
+fn test_parse_errors_have_positions() {
+    let error = Graph::from_dot("graph {\n    a -- b\n    c -> d\n}").unwrap_err();
+    assert_eq!((error.line, error.column), (3, 7));
+    assert_eq!(
+        error.to_string(),
+        "3:7: expected `--` in a graph, found `->`"
+    );
+}
+
+#[test]
+fn test_edges_record_direction() {
+    let graph = Graph::new().with_edges(&[
+        Edge::new("a", "b"),
+        Edge::directed("b", "c").with_attrs(&[("color", "red")]),
+    ]);
+    assert!(!graph.edges[0].is_directed());
+    assert!(graph.edges[1].is_directed());
+    assert_eq!(graph.edges[1].vertices(), ("b", "c"));
+    assert_eq!(
+        graph.to_dot(),
+        "graph {\n    a -- b;\n    b -- c [color=red, dir=forward];\n}\n"
+    );
+
+    let parsed = Graph::from_dot(&graph.to_dot()).unwrap();
+    assert_eq!(parsed.edges, graph.edges);
+    let parsed = Graph::from_dot("digraph { a -> b [dir=none]; b -> c [dir=back] }").unwrap();
+    assert_eq!(
+        parsed.edges,
+        vec![
+            Edge::new("a", "b"),
+            Edge::directed("b", "c").with_attrs(&[("dir", "back")]),
+        ]
+    );
+}
+
+#[test]
+fn test_subgraphs_to_dot() {
+    let graph = Graph::new()
+        .with_kind(GraphKind::Digraph)
+        .with_nodes(&[Node::new("web"), Node::new("api"), Node::new("db")])
+        .with_subgraphs(&[Subgraph::cluster("backend")
+            .with_attrs(&[("label", "Back end")])
+            .with_nodes(&["api"])
+            .with_subgraphs(&[Subgraph::new("storage").with_nodes(&["db"])])])
+        .with_edges(&[Edge::directed("web", "api"), Edge::directed("api", "db")]);
+
+    assert!(graph.subgraphs[0].is_cluster());
+    assert!(!graph.subgraphs[0].subgraphs[0].is_cluster());
+    assert_eq!(
+        graph.to_dot(),
+        r#"digraph {
+    web;
+    api;
+    db;
+    subgraph cluster_backend {
+        label="Back end";
+        api;
+        subgraph storage {
+            db;
+        }
+    }
+    web -> api;
+    api -> db;
+}
+"#
+    );
+}
+
+#[test]
+fn test_parse_subgraphs() {
+    let graph = Graph::from_dot(
+        r#"digraph {
+            node [shape=box]
+            subgraph cluster_backend {
+                label="Back end"
+                node [shape=cylinder]
+                db
+                api -> db
+            }
+            web -> { api cache }
+            cache
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        graph.nodes,
+        vec![
+            Node::new("db").with_attrs(&[("shape", "cylinder")]),
+            Node::new("api").with_attrs(&[("shape", "cylinder")]),
+            Node::new("web").with_attrs(&[("shape", "box")]),
+            Node::new("cache").with_attrs(&[("shape", "box")]),
+        ]
+    );
+    assert_eq!(
+        graph.subgraphs,
+        vec![
+            Subgraph::cluster("backend")
+                .with_attrs(&[("label", "Back end")])
+                .with_nodes(&["db", "api"]),
+            Subgraph::anonymous().with_nodes(&["api", "cache"]),
+        ]
+    );
+    assert_eq!(
+        graph.edges,
+        vec![
+            Edge::directed("api", "db"),
+            Edge::directed("web", "api"),
+            Edge::directed("web", "cache"),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_reports_dangling_edges() {
+    let graph = Graph::new()
+        .with_nodes(&[Node::new("a"), Node::new("b")])
+        .with_edges(&[Edge::new("a", "b"), Edge::directed("b", "c")])
+        .with_subgraphs(&[
+            Subgraph::cluster("x").with_subgraphs(&[Subgraph::anonymous().with_nodes(&["a", "d"])])
+        ]);
+
+    assert_eq!(
+        graph.validate(),
+        Err(vec![
+            ValidationError::DanglingEdge {
+                from: "b".to_string(),
+                to: "c".to_string(),
+                missing: "c".to_string(),
+            },
+            ValidationError::UnknownSubgraphNode {
+                subgraph: None,
+                node: "d".to_string(),
+            },
+        ])
+    );
+    assert_eq!(Graph::new().validate(), Ok(()));
+    assert!(Graph::from_dot("graph { a -- { b c } }")
+        .unwrap()
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn test_duplicate_nodes_are_merged() {
+    let graph = Graph::new().with_nodes(&[
+        Node::new("a").with_attrs(&[("color", "red")]),
+        Node::new("b"),
+        Node::new("a").with_attrs(&[("shape", "box")]),
+    ]);
+
+    assert_eq!(
+        graph.nodes,
+        vec![
+            Node::new("a").with_attrs(&[("color", "red"), ("shape", "box")]),
+            Node::new("b"),
+        ]
+    );
+}
+
+#[test]
+fn test_nodes_can_be_edited_in_place() {
+    let mut graph = Graph::new().with_nodes(&[Node::new("a"), Node::new("b")]);
+    assert!(!graph.add_node(Node::new("b").with_attrs(&[("color", "red")])));
+    assert!(graph.add_node(Node::new("c")));
+
+    let a = graph.get_node_mut("a").expect("node a is there");
+    assert_eq!(a.set_attr("label", "start"), None);
+    assert_eq!(a.set_attr("label", "begin"), Some("start".to_string()));
+    assert!(graph.get_node_mut("z").is_none());
+
+    assert_eq!(
+        graph.get_node("a").unwrap().get_attr("label"),
+        Some("begin")
+    );
+    assert_eq!(graph.get_node("b").unwrap().get_attr("color"), Some("red"));
+    assert_eq!(graph.get_node("c").unwrap().name(), "c");
+    assert_eq!(graph.get_node("c").unwrap().name, "c");
+}
+
+#[test]
+fn test_nodes_changed_directly_are_still_found() {
+    let mut graph = Graph::new().with_nodes(&[Node::new("a"), Node::new("b"), Node::new("c")]);
+    graph.nodes.push(Node::new("d"));
+    graph.nodes.remove(0);
+    graph.nodes[0].name = "bee".to_string();
+
+    assert_eq!(graph.get_node("a"), None);
+    assert_eq!(graph.get_node("b"), None);
+    assert_eq!(graph.get_node("bee"), Some(Node::new("bee")));
+    assert_eq!(graph.get_node("d"), Some(Node::new("d")));
+    assert!(graph.add_edge(Edge::new("bee", "d")).is_ok());
+    assert!(!graph.add_node(Node::new("c").with_attrs(&[("color", "red")])));
+    assert_eq!(graph.nodes[1].get_attr("color"), Some("red"));
+
+    graph.get_node_mut("d").unwrap().name = "dee".to_string();
+    assert_eq!(graph.remove_node("dee"), Some(Node::new("dee")));
+    assert_eq!(
+        graph.nodes,
+        vec![
+            Node::new("bee"),
+            Node::new("c").with_attrs(&[("color", "red")])
+        ]
+    );
+}
+
+#[test]
+fn test_remove_node_removes_its_edges() {
+    let mut graph = Graph::new()
+        .with_nodes(&[Node::new("a"), Node::new("b"), Node::new("c")])
+        .with_edges(&[
+            Edge::new("a", "b"),
+            Edge::new("b", "c"),
+            Edge::new("c", "a"),
+        ])
+        .with_subgraphs(&[Subgraph::new("s").with_nodes(&["b", "c"])]);
+
+    assert_eq!(graph.remove_node("b"), Some(Node::new("b")));
+    assert_eq!(graph.remove_node("b"), None);
+    assert!(graph.get_node("b").is_none());
+    assert_eq!(graph.nodes, vec![Node::new("a"), Node::new("c")]);
+    assert_eq!(graph.edges, vec![Edge::new("c", "a")]);
+    assert_eq!(graph.subgraphs[0].nodes, vec!["c".to_string()]);
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_add_edge_checks_both_ends() {
+    let mut graph = Graph::new().with_nodes(&[Node::new("a"), Node::new("b")]);
+
+    assert_eq!(graph.add_edge(Edge::new("a", "b")), Ok(()));
+    assert_eq!(
+        graph.add_edge(Edge::directed("b", "c")),
+        Err(ValidationError::DanglingEdge {
+            from: "b".to_string(),
+            to: "c".to_string(),
+            missing: "c".to_string(),
+        })
+    );
+    assert_eq!(graph.edges, vec![Edge::new("a", "b")]);
+}
+
+#[test]
+fn test_add_edge_merges_repeats_in_strict_graphs() {
+    let mut graph = Graph::new()
+        .with_strict(true)
+        .with_nodes(&[Node::new("a"), Node::new("b")]);
+
+    graph
+        .add_edge(Edge::new("a", "b").with_attrs(&[("color", "red")]))
+        .unwrap();
+    graph
+        .add_edge(Edge::new("b", "a").with_attrs(&[("weight", "2")]))
+        .unwrap();
+
+    assert_eq!(
+        graph.edges,
+        vec![Edge::new("a", "b").with_attrs(&[("color", "red"), ("weight", "2")])]
+    );
+}
+
+#[test]
+fn test_edge_attributes_can_be_edited() {
+    let mut edge = Edge::directed("a", "b").with_attrs(&[("color", "red")]);
+    assert_eq!(edge.vertices(), ("a", "b"));
+    assert!(edge.is_directed());
+
+    assert_eq!(edge.set_attr("color", "blue"), Some("red".to_string()));
+    edge.set_attr("weight", "3");
+    let mut attrs: Vec<_> = edge.attrs().collect();
+    attrs.sort();
+    assert_eq!(attrs, vec![("color", "blue"), ("weight", "3")]);
+
+    assert_eq!(edge.remove_attr("color"), Some("blue".to_string()));
+    assert_eq!(edge.remove_attr("color"), None);
+    assert_eq!(edge.get_attr("color"), None);
+}
@@ -1,24 +1,59 @@
 use std::convert::TryFrom;
+use std::fmt;
 
 pub type Rank = u8;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
 }
 
 impl Card {
+    pub const JOKER: Card = Card {
+        rank: 0,
+        suit: Suit::Joker,
+    };
+
+    /// Parses a card like `10H`, `AS` or `Q♦`. Panics on anything else, see `Card::try_from`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(c: &str) -> Card {
-        let (rank, suit) = c.split_at(c.len() - 1);
+        Card::try_from(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn is_joker(&self) -> bool {
+        self.suit == Suit::Joker
+    }
+
+    /// All 52 cards of a standard deck, ace high.
+    pub fn deck() -> Vec<Card> {
+        [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club]
+            .iter()
+            .flat_map(|&suit| (2..=14).map(move |rank| Card { rank, suit }))
+            .collect()
+    }
+}
+
+impl TryFrom<&str> for Card {
+    type Error = String;
+
+    fn try_from(c: &str) -> Result<Self, Self::Error> {
+        if c == "JK" || c == "🃏" {
+            return Ok(Card::JOKER);
+        }
+
+        let suit = c.chars().last().ok_or("Empty card")?;
+        let rank = &c[..c.len() - suit.len_utf8()];
 
-        let suit =
-            Suit::try_from(suit.chars().last().expect("idek yet")).expect("Not a valid suit");
+        let suit = Suit::try_from(suit)?;
         let rank = if let Ok(d) = rank.parse::<Rank>() {
-            if d >= 1 && d <= 13 {
+            // a 1 is an ace, which is always ranked high
+            if d == 1 {
+                14
+            } else if (2..=13).contains(&d) {
                 d
             } else {
-                panic!(format!("Invalid rank: {}", d))
+                return Err(format!("Invalid rank: {}", d));
             }
         } else if let Ok(c) = rank.parse::<char>() {
             match c {
@@ -26,21 +61,39 @@ impl Card {
                 'J' => 11,
                 'Q' => 12,
                 'K' => 13,
-                _ => panic!(format!("Invalid rank: {}", c)),
+                _ => return Err(format!("Invalid rank: {}", c)),
             }
         } else {
-            panic!("Not a valid rank")
+            return Err(format!("Not a valid rank: {:?}", rank));
         };
-        Card { rank, suit }
+        Ok(Card { rank, suit })
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// Renders the card with a Unicode suit symbol, like `10♥` or `A♠`.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_joker() {
+            return write!(f, "🃏");
+        }
+        match self.rank {
+            1 | 14 => write!(f, "A")?,
+            11 => write!(f, "J")?,
+            12 => write!(f, "Q")?,
+            13 => write!(f, "K")?,
+            r => write!(f, "{}", r)?,
+        }
+        write!(f, "{}", self.suit)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Suit {
     Heart,
     Diamond,
     Club,
     Spade,
+    Joker,
 }
 
 impl TryFrom<char> for Suit {
@@ -48,15 +101,28 @@ impl TryFrom<char> for Suit {
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'H' => Ok(Suit::Heart),
-            'S' => Ok(Suit::Spade),
-            'C' => Ok(Suit::Club),
-            'D' => Ok(Suit::Diamond),
+            'H' | '♥' | '♡' => Ok(Suit::Heart),
+            'S' | '♠' | '♤' => Ok(Suit::Spade),
+            'C' | '♣' | '♧' => Ok(Suit::Club),
+            'D' | '♦' | '♢' => Ok(Suit::Diamond),
             _ => Err("Invalid char conversion to suit."),
         }
     }
 }
 
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Suit::Heart => "♥",
+            Suit::Diamond => "♦",
+            Suit::Club => "♣",
+            Suit::Spade => "♠",
+            Suit::Joker => "🃏",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,5 +144,71 @@ mod tests {
         test("AD", 14, Suit::Diamond);
         test("JS", 11, Suit::Spade);
         test("5H", 5, Suit::Heart);
+        assert!(Card::from_str("JK").is_joker());
+        assert!(!Card::from_str("JC").is_joker());
+    }
+
+    #[test]
+    fn suit_from_symbol_works() {
+        assert_eq!(Suit::try_from('♦').unwrap(), Suit::Diamond);
+        assert_eq!(Suit::try_from('♥').unwrap(), Suit::Heart);
+        assert_eq!(Suit::try_from('♣').unwrap(), Suit::Club);
+        assert_eq!(Suit::try_from('♠').unwrap(), Suit::Spade);
+        assert_eq!(Suit::try_from('♡').unwrap(), Suit::Heart);
+    }
+
+    #[test]
+    fn card_from_symbols_works() {
+        assert_eq!(Card::from_str("10♣"), Card::from_str("10C"));
+        assert_eq!(Card::from_str("A♦"), Card::from_str("AD"));
+        assert_eq!(Card::from_str("J♠"), Card::from_str("JS"));
+        assert_eq!(Card::from_str("5♥"), Card::from_str("5H"));
+        assert_eq!(Card::from_str("🃏"), Card::JOKER);
+    }
+
+    #[test]
+    fn rank_one_is_an_ace() {
+        assert_eq!(Card::from_str("1H"), Card::from_str("AH"));
+        assert_eq!(Card::from_str("1S").to_string(), "A♠");
+    }
+
+    #[test]
+    fn card_try_from_reports_errors() {
+        assert!(Card::try_from("").is_err());
+        assert!(Card::try_from("10X").is_err());
+        assert!(Card::try_from("15H").is_err());
+        assert!(Card::try_from("0H").is_err());
+        assert!(Card::try_from("ZH").is_err());
+        assert!(Card::try_from("♥").is_err());
+    }
+
+    #[test]
+    fn card_display_uses_suit_symbols() {
+        let test = |c: &str, s: &str| assert_eq!(Card::from_str(c).to_string(), s);
+
+        test("10C", "10♣");
+        test("AD", "A♦");
+        test("JS", "J♠");
+        test("QH", "Q♥");
+        test("KH", "K♥");
+        test("5H", "5♥");
+        test("JK", "🃏");
+    }
+
+    #[test]
+    fn card_display_round_trips() {
+        for card in Card::deck() {
+            assert_eq!(Card::from_str(&card.to_string()), card);
+        }
+    }
+
+    #[test]
+    fn deck_has_52_distinct_cards() {
+        let deck = Card::deck();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(
+            deck.iter().collect::<std::collections::HashSet<_>>().len(),
+            52
+        );
     }
 }
@@ -0,0 +1,303 @@
+use crate::card::Card;
+use crate::lookup::{best_of, encode, Strength};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use std::collections::HashSet;
+
+const BOARD_SIZE: usize = 5;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EquityError {
+    NotEnoughPlayers,
+    BoardTooLarge(usize),
+    DuplicateCard(Card),
+    Joker,
+    NotEnoughCards,
+    NoSamples,
+}
+
+/// How often a player wins, ties and loses over all runouts of the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Equity {
+    pub win: f64,
+    pub tie: f64,
+    pub lose: f64,
+    /// Expected share of the pot, with ties split evenly between the winners.
+    pub share: f64,
+}
+
+/// Hold'em equity for every player given their hole cards and a board of zero to five cards.
+///
+/// When there are at most `samples` ways to complete the board, every one of them is dealt
+/// and the result is exact. Otherwise `samples` random runouts are dealt from an RNG seeded
+/// with `seed`, so the same inputs always give the same answer.
+pub fn equity(
+    hole_cards: &[[Card; 2]],
+    board: &[Card],
+    samples: u64,
+    seed: u64,
+) -> Result<Vec<Equity>, EquityError> {
+    if hole_cards.len() < 2 {
+        return Err(EquityError::NotEnoughPlayers);
+    }
+    if board.len() > BOARD_SIZE {
+        return Err(EquityError::BoardTooLarge(board.len()));
+    }
+    if samples == 0 {
+        return Err(EquityError::NoSamples);
+    }
+
+    let mut dealt = HashSet::new();
+    for card in hole_cards.iter().flatten().chain(board.iter()) {
+        if card.is_joker() {
+            return Err(EquityError::Joker);
+        }
+        if !dealt.insert(*card) {
+            return Err(EquityError::DuplicateCard(*card));
+        }
+    }
+
+    let deck: Vec<u32> = Card::deck()
+        .iter()
+        .filter(|c| !dealt.contains(c))
+        .filter_map(encode)
+        .collect();
+    let holes: Vec<[u32; 2]> = hole_cards
+        .iter()
+        .map(|[a, b]| Some([encode(a)?, encode(b)?]))
+        .collect::<Option<_>>()
+        .ok_or(EquityError::Joker)?;
+    let board: Vec<u32> = board
+        .iter()
+        .map(encode)
+        .collect::<Option<_>>()
+        .ok_or(EquityError::Joker)?;
+    let missing = BOARD_SIZE - board.len();
+    if deck.len() < missing {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    let mut tally = Tally::new(holes.len());
+    if binomial(deck.len() as u64, missing as u64) <= samples {
+        for_each_runout(&deck, missing, |runout| {
+            tally.record(&holes, &board, runout)
+        });
+    } else {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut deck = deck;
+        for _ in 0..samples {
+            let (runout, _) = deck.partial_shuffle(&mut rng, missing);
+            tally.record(&holes, &board, runout);
+        }
+    }
+    Ok(tally.equities())
+}
+
+struct Tally {
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    shares: Vec<f64>,
+    runouts: u64,
+}
+
+impl Tally {
+    fn new(players: usize) -> Self {
+        Tally {
+            wins: vec![0; players],
+            ties: vec![0; players],
+            shares: vec![0.0; players],
+            runouts: 0,
+        }
+    }
+
+    fn record(&mut self, holes: &[[u32; 2]], board: &[u32], runout: &[u32]) {
+        let mut cards = [0; 7];
+        cards[2..2 + board.len()].copy_from_slice(board);
+        cards[2 + board.len()..].copy_from_slice(runout);
+
+        let strengths: Vec<Strength> = holes
+            .iter()
+            .map(|hole| {
+                cards[..2].copy_from_slice(hole);
+                best_of(&cards)
+            })
+            .collect();
+        let best = *strengths.iter().max().unwrap();
+        let winners = strengths.iter().filter(|&&s| s == best).count();
+
+        for (player, &strength) in strengths.iter().enumerate() {
+            if strength == best {
+                if winners == 1 {
+                    self.wins[player] += 1;
+                } else {
+                    self.ties[player] += 1;
+                }
+                self.shares[player] += 1.0 / winners as f64;
+            }
+        }
+        self.runouts += 1;
+    }
+
+    fn equities(&self) -> Vec<Equity> {
+        let n = self.runouts as f64;
+        (0..self.wins.len())
+            .map(|p| {
+                let (win, tie) = (self.wins[p] as f64 / n, self.ties[p] as f64 / n);
+                Equity {
+                    win,
+                    tie,
+                    lose: 1.0 - win - tie,
+                    share: self.shares[p] / n,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Calls `f` with every way of choosing `k` cards from `deck`.
+fn for_each_runout(deck: &[u32], k: usize, mut f: impl FnMut(&[u32])) {
+    let n = deck.len();
+    let mut indices: Vec<usize> = (0..k).collect();
+    let mut runout = vec![0; k];
+    loop {
+        for (card, &i) in runout.iter_mut().zip(indices.iter()) {
+            *card = deck[i];
+        }
+        f(&runout);
+
+        let i = match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => i,
+            None => return,
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+fn binomial(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hole(s: &str) -> [Card; 2] {
+        let cards: Vec<Card> = s.split_whitespace().map(Card::from_str).collect();
+        [cards[0], cards[1]]
+    }
+
+    fn board(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(Card::from_str).collect()
+    }
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() < tolerance
+    }
+
+    #[test]
+    fn binomial_works() {
+        assert_eq!(binomial(52, 5), 2_598_960);
+        assert_eq!(binomial(44, 1), 44);
+        assert_eq!(binomial(44, 0), 1);
+    }
+
+    #[test]
+    fn for_each_runout_visits_every_combination_once() {
+        let deck: Vec<u32> = (0..8).collect();
+        let mut seen = HashSet::new();
+        for_each_runout(&deck, 3, |r| assert!(seen.insert(r.to_vec())));
+        assert_eq!(seen.len(), 56);
+
+        let mut count = 0;
+        for_each_runout(&deck, 0, |_| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn turn_is_enumerated_exactly() {
+        let players = [hole("AS AH"), hole("KS KH")];
+        let result = equity(&players, &board("2C 7D 9S JC"), 1000, 0).unwrap();
+        // only the two remaining kings out of 44 rivers save the kings
+        assert_eq!(result[0].win, 42.0 / 44.0);
+        assert_eq!(result[1].win, 2.0 / 44.0);
+        assert_eq!(result[0].tie, 0.0);
+        assert!(close(result[0].lose, result[1].win, 1e-12));
+    }
+
+    #[test]
+    fn exhaustive_does_not_depend_on_seed() {
+        let players = [hole("AS KD"), hole("QH QC"), hole("7S 8S")];
+        let flop = board("2S 9S QD");
+        assert_eq!(
+            equity(&players, &flop, 10_000, 1),
+            equity(&players, &flop, 10_000, 2)
+        );
+    }
+
+    #[test]
+    fn equities_add_up() {
+        let players = [hole("AS KD"), hole("QH QC"), hole("7S 8S")];
+        let result = equity(&players, &board("2S 9S QD"), 10_000, 0).unwrap();
+        for e in result.iter() {
+            assert!(close(e.win + e.tie + e.lose, 1.0, 1e-9));
+        }
+        let shares: f64 = result.iter().map(|e| e.share).sum();
+        assert!(close(shares, 1.0, 1e-9));
+    }
+
+    #[test]
+    fn board_plays_is_a_tie() {
+        let players = [hole("2C 3D"), hole("4C 5D")];
+        let result = equity(&players, &board("AS KS QS JS 10S"), 1, 0).unwrap();
+        for e in result.iter() {
+            assert_eq!(e.tie, 1.0);
+            assert_eq!(e.share, 0.5);
+        }
+    }
+
+    #[test]
+    fn preflop_aces_against_kings_is_sampled() {
+        let players = [hole("AS AH"), hole("KD KC")];
+        let result = equity(&players, &[], 20_000, 7).unwrap();
+        assert!(close(result[0].share, 0.82, 0.02), "{:?}", result);
+        assert!(close(result[1].share, 0.18, 0.02), "{:?}", result);
+    }
+
+    #[test]
+    fn sampling_is_deterministic_for_a_seed() {
+        let players = [hole("AS AH"), hole("KD KC")];
+        let a = equity(&players, &[], 2_000, 42).unwrap();
+        let b = equity(&players, &[], 2_000, 42).unwrap();
+        let c = equity(&players, &[], 2_000, 43).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn errors() {
+        let players = [hole("AS AH"), hole("KD KC")];
+        assert_eq!(
+            equity(&players[..1], &[], 10, 0),
+            Err(EquityError::NotEnoughPlayers)
+        );
+        assert_eq!(
+            equity(&players, &board("2C 3C 4C 5C 6C 7C"), 10, 0),
+            Err(EquityError::BoardTooLarge(6))
+        );
+        assert_eq!(
+            equity(&players, &board("2C 3C AS"), 10, 0),
+            Err(EquityError::DuplicateCard(Card::from_str("AS")))
+        );
+        assert_eq!(equity(&players, &[], 0, 0), Err(EquityError::NoSamples));
+        assert_eq!(
+            equity(&players, &board("2C JK"), 10, 0),
+            Err(EquityError::Joker)
+        );
+    }
+}
@@ -12,25 +12,48 @@ pub struct Hand<'a> {
 impl<'a> Hand<'a> {
     pub fn from_str(str_hand: &'a str) -> Self {
         Hand {
-            hand: str_hand
-                .split_whitespace()
-                .map(|c| Card::from_str(c))
-                .collect(),
+            hand: str_hand.split_whitespace().map(Card::from_str).collect(),
             str_hand,
         }
     }
 
+    pub(crate) fn from_cards(hand: Vec<Card>) -> Self {
+        Hand { hand, str_hand: "" }
+    }
+
     pub fn get_str(&self) -> &'a str {
         self.str_hand
     }
 
+    pub fn cards(&self) -> &[Card] {
+        &self.hand
+    }
+
     pub fn score_hand(&self) -> Score {
+        self.score_with(true, true)
+    }
+
+    /// Scores the hand, optionally ignoring straights and flushes (ace-to-five low) or
+    /// the ace-low wheel (deuce-to-seven low).
+    pub(crate) fn score_with(&self, straights_and_flushes: bool, wheel: bool) -> Score {
+        let (five_of_a_kind, _) = self.get_pairs_of_n(5);
         let (four_of_a_kind, fours_kickers) = self.get_pairs_of_n(4);
         let (three_of_a_kind, threes_kickers) = self.get_pairs_of_n(3);
         let (pairs, pair_kickers) = self.get_pairs_of_n(2);
         let high_cards = self.get_ranks_sorted_high_to_low();
 
-        if let Some(rank) = self.get_straight_flush() {
+        if let Some(&rank) = five_of_a_kind.first() {
+            return Score::FiveOfAKind(rank);
+        }
+
+        let flush = straights_and_flushes && self.is_flush();
+        let straight = if straights_and_flushes {
+            self.get_straight(wheel)
+        } else {
+            None
+        };
+
+        if let (true, Some(rank)) = (flush, straight) {
             return Score::StraightFlush(rank);
         }
 
@@ -42,11 +65,11 @@ impl<'a> Hand<'a> {
             return Score::FullHouse(threes_rank, pair_rank);
         }
 
-        if let Some(ranks) = self.get_flush() {
-            return Score::Flush(ranks);
+        if flush {
+            return Score::Flush(high_cards);
         }
 
-        if let Some(rank) = self.get_straight() {
+        if let Some(rank) = straight {
             return Score::Straight(rank);
         }
 
@@ -60,19 +83,13 @@ impl<'a> Hand<'a> {
             let fp = *pairs.first().unwrap();
             let lp = *pairs.last().unwrap();
             return Score::TwoPair(fp.max(lp), fp.min(lp), *pair_kickers.first().unwrap());
-        } else if let Some(pair) = pairs.first() {
-            return Score::OnePair(*pair);
+        } else if let Some(&pair) = pairs.first() {
+            let mut kickers = pair_kickers;
+            kickers.sort_unstable_by(|a, b| b.cmp(a));
+            return Score::OnePair(pair, kickers);
         }
 
-        return Score::HighCard(high_cards);
-    }
-
-    fn get_straight_flush(&self) -> Option<Rank> {
-        if self.is_flush() {
-            self.get_straight()
-        } else {
-            None
-        }
+        Score::HighCard(high_cards)
     }
 
     fn is_flush(&self) -> bool {
@@ -88,22 +105,13 @@ impl<'a> Hand<'a> {
             })
     }
 
-    fn get_flush(&self) -> Option<Vec<Rank>> {
-        if self.is_flush() {
-            Some(self.get_ranks_sorted_high_to_low())
-        } else {
-            None
-        }
-    }
-
-    fn get_straight(&self) -> Option<Rank> {
+    fn get_straight(&self, wheel: bool) -> Option<Rank> {
         let mut prev: Option<Rank> = None;
         let mut is_straight = true;
         let ranks = self.get_ranks_sorted_high_to_low();
         for &rank in ranks.iter().as_ref() {
-            dbg!(rank, prev);
             if let Some(prev) = prev {
-                if rank + 1 != prev && !(prev == 14 && rank == 5) {
+                if rank + 1 != prev && !(wheel && prev == 14 && rank == 5) {
                     is_straight = false;
                     break;
                 }
@@ -112,7 +120,11 @@ impl<'a> Hand<'a> {
         }
 
         if is_straight {
-            ranks.first().map(|&d| if d == 14 { 5 } else { d })
+            // an ace followed by a five is the low end of a wheel (A 2 3 4 5)
+            match (ranks[0], ranks[1]) {
+                (14, 5) => Some(5),
+                (high, _) => Some(high),
+            }
         } else {
             None
         }
@@ -139,7 +151,7 @@ impl<'a> Hand<'a> {
                 }
             })
             .collect();
-        (pairs.iter().map(|&n| n).collect(), kickers)
+        (pairs.iter().copied().collect(), kickers)
     }
 
     fn get_pairs_of_n_helper(&self, n: u8) -> HashSet<Rank> {
@@ -0,0 +1,228 @@
+use crate::card::Card;
+use crate::hand::Hand;
+use crate::score::Score;
+
+const HAND_SIZE: usize = 5;
+
+/// The best five card `Score` that can be made from any of the given cards.
+/// Returns `None` when fewer than five cards are given, or when one is a joker, since
+/// jokers aren't in play in either game.
+pub fn best_hand(cards: &[Card]) -> Option<Score> {
+    if cards.iter().any(Card::is_joker) {
+        return None;
+    }
+    combinations(cards, HAND_SIZE)
+        .into_iter()
+        .map(|hand| Hand::from_cards(hand).score_hand())
+        .max()
+}
+
+/// Texas Hold'em: the best five cards out of the hole cards and the board,
+/// using any number of either. Works on a partial board (flop or turn) too.
+pub fn texas_holdem(hole: &[Card], board: &[Card]) -> Option<Score> {
+    let cards: Vec<Card> = hole.iter().chain(board.iter()).copied().collect();
+    best_hand(&cards)
+}
+
+/// Omaha: the best hand using exactly two hole cards and exactly three board cards.
+/// Returns `None` when there are fewer than two hole cards or three board cards, or
+/// when any card is a joker.
+pub fn omaha(hole: &[Card], board: &[Card]) -> Option<Score> {
+    if hole.iter().chain(board.iter()).any(Card::is_joker) {
+        return None;
+    }
+    let boards = combinations(board, 3);
+    combinations(hole, 2)
+        .iter()
+        .flat_map(|h| boards.iter().map(move |b| [&h[..], &b[..]].concat()))
+        .map(|hand| Hand::from_cards(hand).score_hand())
+        .max()
+}
+
+/// Every way of choosing `k` cards out of `cards`, keeping the original order.
+fn combinations(cards: &[Card], k: usize) -> Vec<Vec<Card>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if cards.len() < k {
+        return vec![];
+    }
+
+    let (first, rest) = cards.split_first().unwrap();
+    let mut with_first: Vec<Vec<Card>> = combinations(rest, k - 1)
+        .into_iter()
+        .map(|mut c| {
+            c.insert(0, *first);
+            c
+        })
+        .collect();
+    with_first.extend(combinations(rest, k));
+    with_first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(Card::from_str).collect()
+    }
+
+    fn holdem(hole: &str, board: &str) -> Score {
+        texas_holdem(&cards(hole), &cards(board)).unwrap()
+    }
+
+    fn plo(hole: &str, board: &str) -> Score {
+        omaha(&cards(hole), &cards(board)).unwrap()
+    }
+
+    #[test]
+    fn combinations_counts() {
+        let deck = cards("2H 3H 4H 5H 6H 7H 8H");
+        assert_eq!(combinations(&deck, 5).len(), 21);
+        assert_eq!(combinations(&deck[..4], 2).len(), 6);
+        assert_eq!(combinations(&deck[..5], 3).len(), 10);
+        assert!(combinations(&deck[..2], 3).is_empty());
+    }
+
+    #[test]
+    fn best_hand_needs_five_cards() {
+        assert_eq!(best_hand(&cards("AS KS QS JS")), None);
+        assert_eq!(texas_holdem(&cards("AS KS"), &cards("QS JS")), None);
+    }
+
+    #[test]
+    fn jokers_are_not_in_play() {
+        assert_eq!(best_hand(&cards("AS KS QS JS JK")), None);
+        assert_eq!(
+            texas_holdem(&cards("AS JK"), &cards("QS JS 10S 2H 3D")),
+            None
+        );
+        assert_eq!(
+            texas_holdem(&cards("AS KS"), &cards("QS JS 10S 2H JK")),
+            None
+        );
+        assert_eq!(omaha(&cards("AS KS JK 2C"), &cards("QS JS 10S")), None);
+        assert_eq!(omaha(&cards("AS KS 3D 2C"), &cards("QS JS 10S JK")), None);
+    }
+
+    #[test]
+    fn holdem_straight_flush() {
+        assert_eq!(holdem("AS KS", "QS JS 10S 2H 3D"), Score::StraightFlush(14));
+    }
+
+    #[test]
+    fn holdem_board_plays() {
+        assert_eq!(holdem("2C 3D", "AS KS QS JS 10S"), Score::StraightFlush(14));
+    }
+
+    #[test]
+    fn holdem_partial_board() {
+        assert_eq!(holdem("AS KS", "QS JS 10S"), Score::StraightFlush(14));
+        assert_eq!(
+            holdem("AS KD", "AC 7H 2D 9S"),
+            Score::OnePair(14, vec![13, 9, 7])
+        );
+    }
+
+    #[test]
+    fn holdem_four_of_a_kind_takes_best_kicker() {
+        assert_eq!(holdem("AH 2D", "9C 9S 9D 9H KC"), Score::FourOfAKind(9, 14));
+    }
+
+    #[test]
+    fn holdem_full_house_from_two_trips() {
+        assert_eq!(holdem("KH KD", "KC 7S 7D 7H 2C"), Score::FullHouse(13, 7));
+    }
+
+    #[test]
+    fn holdem_six_card_flush_takes_top_five() {
+        assert_eq!(
+            holdem("AH 2H", "KH 9H 5H 3H QC"),
+            Score::Flush(vec![14, 13, 9, 5, 3])
+        );
+    }
+
+    #[test]
+    fn holdem_six_card_straight_takes_highest() {
+        assert_eq!(holdem("6H 7D", "2C 3S 4H 5D 8C"), Score::Straight(8));
+    }
+
+    #[test]
+    fn holdem_wheel_beats_pair() {
+        assert_eq!(holdem("AH 2D", "3C 4S 5H KD KC"), Score::Straight(5));
+    }
+
+    #[test]
+    fn holdem_trips() {
+        assert_eq!(
+            holdem("5H 5D", "5C KS 9D 4H 2C"),
+            Score::ThreeOfAKind(5, 13, 9)
+        );
+    }
+
+    #[test]
+    fn holdem_three_pairs_plays_best_two() {
+        assert_eq!(holdem("QH QD", "8C 8S 4D 4H AC"), Score::TwoPair(12, 8, 14));
+        assert_eq!(holdem("QH QD", "8C 8S 4D 4H 2C"), Score::TwoPair(12, 8, 4));
+    }
+
+    #[test]
+    fn holdem_high_card() {
+        assert_eq!(
+            holdem("2H 7D", "AC KS 9D 4H JC"),
+            Score::HighCard(vec![14, 13, 11, 9, 7])
+        );
+    }
+
+    #[test]
+    fn holdem_kicker_decides_between_players() {
+        let board = "AS 9C 7D 4H 2S";
+        assert!(holdem("AH KD", board) > holdem("AC QD", board));
+        assert_eq!(holdem("AH 3D", board), holdem("AC 3H", board));
+    }
+
+    #[test]
+    fn omaha_needs_two_hole_and_three_board_cards() {
+        assert_eq!(omaha(&cards("AS"), &cards("KS QS JS 10S")), None);
+        assert_eq!(omaha(&cards("AS KS QS JS"), &cards("10S 9S")), None);
+    }
+
+    #[test]
+    fn omaha_flush_needs_two_suited_hole_cards() {
+        let (hole, board) = ("AH KD QC JS", "2H 5H 8H 9H 3C");
+        assert_eq!(plo(hole, board), Score::HighCard(vec![14, 13, 9, 8, 5]));
+        assert_eq!(holdem(hole, board), Score::Flush(vec![14, 9, 8, 5, 2]));
+    }
+
+    #[test]
+    fn omaha_flush() {
+        assert_eq!(
+            plo("AH KH 2C 3D", "QH 7H 4H JS 9C"),
+            Score::Flush(vec![14, 13, 12, 7, 4])
+        );
+    }
+
+    #[test]
+    fn omaha_board_quads_play_as_trips() {
+        assert_eq!(
+            plo("2C 3D 7H 8S", "KC KD KH KS 4C"),
+            Score::ThreeOfAKind(13, 8, 7)
+        );
+    }
+
+    #[test]
+    fn omaha_straight_uses_exactly_two_hole_cards() {
+        let (hole, board) = ("9H 3C 4D 4S", "5C 6D 7H 8S KD");
+        assert_eq!(plo(hole, board), Score::Straight(7));
+        assert_eq!(holdem(hole, board), Score::Straight(9));
+    }
+
+    #[test]
+    fn omaha_full_house() {
+        assert_eq!(
+            plo("8C 8D AS KS", "8H QC QD QH 2S"),
+            Score::FullHouse(12, 8)
+        );
+    }
+}
@@ -1,25 +1,49 @@
 mod card;
+pub mod equity;
 mod hand;
+pub mod holdem;
+pub mod lookup;
+pub mod rules;
 mod score;
 use hand::Hand;
+use rules::{RuleSet, RulesError};
 
+pub use card::{Card, Rank, Suit};
+pub use score::Score;
+
+/// Panics on a card that doesn't parse, or on a joker, since jokers aren't wild in the
+/// standard rules.
 pub fn winning_hands<'a>(hands: &[&'a str]) -> Option<Vec<&'a str>> {
-    get_best_hands(hands.iter().map(|h| Hand::from_str(h)).collect())
-        .map(|hands| hands.iter().map(|h| h.get_str()).collect())
+    winning_hands_with(hands, &RuleSet::default()).unwrap_or_else(|e| panic!("{:?}", e))
+}
+
+pub fn winning_hands_with<'a>(
+    hands: &[&'a str],
+    rules: &RuleSet,
+) -> Result<Option<Vec<&'a str>>, RulesError> {
+    let best = get_best_hands(hands.iter().map(|h| Hand::from_str(h)).collect(), rules)?;
+    Ok(best.map(|hands| hands.iter().map(|h| h.get_str()).collect()))
 }
 
-fn get_best_hands(mut hands: Vec<Hand>) -> Option<Vec<Hand>> {
-    hands.sort_by(|a, b| b.score_hand().cmp(&a.score_hand()));
+fn get_best_hands<'a>(
+    hands: Vec<Hand<'a>>,
+    rules: &RuleSet,
+) -> Result<Option<Vec<Hand<'a>>>, RulesError> {
+    let mut scored = hands
+        .into_iter()
+        .map(|h| Ok((rules.score(h.cards())?, h)))
+        .collect::<Result<Vec<_>, _>>()?;
+    scored.sort_by(|a, b| rules.compare_scores(&b.0, &a.0));
 
-    if let Some(first) = hands.first() {
-        Some(
-            hands
-                .iter()
-                .take_while(|&h| h.score_hand() == first.score_hand())
-                .cloned()
-                .collect(),
-        )
-    } else {
-        None
-    }
+    let best = match scored.first() {
+        Some((score, _)) => score.clone(),
+        None => return Ok(None),
+    };
+    Ok(Some(
+        scored
+            .into_iter()
+            .take_while(|(score, _)| *score == best)
+            .map(|(_, h)| h)
+            .collect(),
+    ))
 }
@@ -0,0 +1,392 @@
+//! Table driven five card evaluator, after Cactus Kev.
+//!
+//! Each card is packed into a `u32`:
+//!
+//! ```text
+//! +--------+--------+--------+--------+
+//! |xxxbbbbb|bbbbbbbb|cdhsrrrr|xxpppppp|
+//! +--------+--------+--------+--------+
+//! ```
+//!
+//! where `b` is one bit per rank, `cdhs` is one bit per suit, `r` is the rank (deuce = 0)
+//! and `p` is the rank's prime. Flushes and hands of five distinct ranks are looked up
+//! directly by the or'd rank bits. Every other hand is identified by the product of its
+//! primes, which is unique per multiset of ranks, and found with a binary search.
+//!
+//! The tables are built once from `Score`, so a `Strength` always orders hands exactly
+//! as `Score` does. Higher is better, from 1 (7 5 4 3 2) to 7462 (a royal flush).
+//!
+//! Jokers have no bit pattern, and the same card twice is not a hand, so those give
+//! `None` rather than a strength.
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::Hand;
+use crate::score::Score;
+
+use std::sync::OnceLock;
+
+pub type Strength = u16;
+
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const RANK_BITS: usize = 1 << 13;
+const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
+
+struct Tables {
+    flushes: Vec<Strength>,
+    unique5: Vec<Strength>,
+    products: Vec<(u32, Strength)>, // sorted by product
+}
+
+/// Packs a card into its bit pattern. Ranks run from 2 to 14 (ace).
+/// `None` for a joker, or a rank outside of that.
+pub fn encode(card: &Card) -> Option<u32> {
+    let suit = match card.suit {
+        Suit::Spade => 0x1,
+        Suit::Heart => 0x2,
+        Suit::Diamond => 0x4,
+        Suit::Club => 0x8,
+        Suit::Joker => return None,
+    };
+    let r = u32::from(card.rank.checked_sub(2)?);
+    if r >= 13 {
+        return None;
+    }
+    Some((1 << (16 + r)) | (suit << 12) | (r << 8) | PRIMES[r as usize])
+}
+
+/// Whether `code` is the bit pattern of some card, as made by [encode]
+fn is_code(code: u32) -> bool {
+    let r = (code >> 8) & 0xF;
+    let suit = (code >> 12) & 0xF;
+    r < 13
+        && suit.count_ones() == 1
+        && code == (1 << (16 + r)) | (suit << 12) | (r << 8) | PRIMES[r as usize]
+}
+
+/// Whether `codes` are all bit patterns of cards, with no card twice
+fn are_cards(codes: &[u32]) -> bool {
+    codes
+        .iter()
+        .enumerate()
+        .all(|(i, &code)| is_code(code) && !codes[..i].contains(&code))
+}
+
+/// Strength of five encoded cards, or `None` unless they are five different cards.
+pub fn evaluate(cards: [u32; 5]) -> Option<Strength> {
+    if !are_cards(&cards) {
+        return None;
+    }
+    Some(strength(cards))
+}
+
+/// Strength of five different cards, which have to have been checked by [are_cards].
+/// Anything else is looked up as 0, which is weaker than every hand.
+fn strength(cards: [u32; 5]) -> Strength {
+    let tables = tables();
+    let [c1, c2, c3, c4, c5] = cards;
+    let index = ((c1 | c2 | c3 | c4 | c5) >> 16) as usize;
+
+    if c1 & c2 & c3 & c4 & c5 & 0xF000 != 0 {
+        return tables.flushes[index];
+    }
+
+    let unique = tables.unique5[index];
+    if unique != 0 {
+        return unique;
+    }
+
+    let product = (c1 & 0xFF) * (c2 & 0xFF) * (c3 & 0xFF) * (c4 & 0xFF) * (c5 & 0xFF);
+    tables
+        .products
+        .binary_search_by_key(&product, |&(p, _)| p)
+        .map_or(0, |i| tables.products[i].1)
+}
+
+/// Strength of five cards, or `None` if one is a joker or two are the same card.
+pub fn evaluate_cards(cards: &[Card; 5]) -> Option<Strength> {
+    let [c1, c2, c3, c4, c5] = cards;
+    evaluate([
+        encode(c1)?,
+        encode(c2)?,
+        encode(c3)?,
+        encode(c4)?,
+        encode(c5)?,
+    ])
+}
+
+/// Strength of the best five out of five to seven encoded cards, or `None` for any
+/// other number of cards or unless they are all different cards.
+pub fn evaluate_best(cards: &[u32]) -> Option<Strength> {
+    if !(5..=7).contains(&cards.len()) || !are_cards(cards) {
+        return None;
+    }
+    Some(best_of(cards))
+}
+
+/// Strength of the best five out of five to seven cards, which have to have been
+/// checked by [are_cards], like [evaluate_best] does.
+pub(crate) fn best_of(cards: &[u32]) -> Strength {
+    let n = cards.len();
+    let mut best = 0;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let hand = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        best = best.max(strength(hand));
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(build_tables)
+}
+
+/// Scores one representative hand of every rank multiset, with and without a flush,
+/// then numbers the distinct scores from lowest to highest.
+fn build_tables() -> Tables {
+    let mut classes: Vec<(Score, bool, Vec<Rank>)> = rank_multisets()
+        .into_iter()
+        .flat_map(|ranks| {
+            let distinct = ranks.windows(2).all(|w| w[0] != w[1]);
+            let mut hands = vec![(representative(&ranks, false), false, ranks.clone())];
+            if distinct {
+                hands.push((representative(&ranks, true), true, ranks));
+            }
+            hands
+        })
+        .collect();
+    classes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut tables = Tables {
+        flushes: vec![0; RANK_BITS],
+        unique5: vec![0; RANK_BITS],
+        products: Vec::new(),
+    };
+    let mut strength = 0;
+    let mut previous: Option<&Score> = None;
+    for (score, flush, ranks) in classes.iter() {
+        if previous != Some(score) {
+            strength += 1;
+        }
+        previous = Some(score);
+
+        let bits = ranks.iter().fold(0, |acc, r| acc | 1 << (r - 2));
+        let product = ranks.iter().map(|&r| PRIMES[(r - 2) as usize]).product();
+        if *flush {
+            tables.flushes[bits] = strength;
+        } else if ranks.windows(2).all(|w| w[0] != w[1]) {
+            tables.unique5[bits] = strength;
+        } else {
+            tables.products.push((product, strength));
+        }
+    }
+    tables.products.sort_unstable();
+    tables
+}
+
+/// Every multiset of five ranks with no rank used more than four times, low to high.
+fn rank_multisets() -> Vec<Vec<Rank>> {
+    fn extend(ranks: &mut Vec<Rank>, from: Rank, out: &mut Vec<Vec<Rank>>) {
+        if ranks.len() == 5 {
+            out.push(ranks.clone());
+            return;
+        }
+        for r in from..=14 {
+            if ranks.iter().filter(|&&x| x == r).count() < 4 {
+                ranks.push(r);
+                extend(ranks, r, out);
+                ranks.pop();
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    extend(&mut Vec::new(), 2, &mut out);
+    out
+}
+
+/// A scored hand with the given ranks. Suits are dealt round robin so that equal ranks
+/// never share a suit and the hand is never a flush, unless `flush` is asked for.
+fn representative(ranks: &[Rank], flush: bool) -> Score {
+    let cards = ranks
+        .iter()
+        .enumerate()
+        .map(|(i, &rank)| Card {
+            rank,
+            suit: if flush { Suit::Heart } else { SUITS[i % 4] },
+        })
+        .collect();
+    Hand::from_cards(cards).score_hand()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    fn strength(hand: &str) -> Strength {
+        let cards: Vec<Card> = hand.split_whitespace().map(Card::from_str).collect();
+        let mut five = [cards[0]; 5];
+        five.copy_from_slice(&cards);
+        evaluate_cards(&five).unwrap()
+    }
+
+    fn code(card: &str) -> u32 {
+        encode(&Card::from_str(card)).unwrap()
+    }
+
+    #[test]
+    fn encode_matches_layout() {
+        assert_eq!(code("KD"), 0x0800_4B25);
+        assert_eq!(code("5S"), 0x0008_1307);
+        assert_eq!(code("JC"), 0x0200_891D);
+    }
+
+    #[test]
+    fn jokers_and_unknown_ranks_have_no_code() {
+        assert_eq!(encode(&Card::JOKER), None);
+        for rank in [0, 1, 15, 255] {
+            let card = Card {
+                rank,
+                suit: Suit::Spade,
+            };
+            assert_eq!(encode(&card), None, "rank {}", rank);
+        }
+    }
+
+    #[test]
+    fn repeated_cards_have_no_strength() {
+        let ace = code("AS");
+        assert_eq!(evaluate([ace; 5]), None);
+        assert_eq!(evaluate([code("AH"); 5]), None);
+        let [a, b, c, d] = [code("AH"), code("AD"), code("AC"), code("KS")];
+        assert_eq!(evaluate([ace, a, b, c, ace]), None);
+        assert_eq!(
+            evaluate([ace, a, b, c, d]),
+            Some(strength("AS AH AD AC KS"))
+        );
+        assert_eq!(evaluate_best(&[ace, a, b, c, d, ace]), None);
+
+        let joker = [Card::JOKER, Card::from_str("AS")];
+        let cards = [joker[1], joker[0], joker[0], joker[0], joker[0]];
+        assert_eq!(evaluate_cards(&cards), None);
+    }
+
+    #[test]
+    fn other_numbers_or_patterns_have_no_strength() {
+        let seven: Vec<u32> = "AS KS 2D 7C QS JS 10S"
+            .split_whitespace()
+            .map(code)
+            .collect();
+        assert_eq!(evaluate_best(&seven[..4]), None);
+        assert_eq!(
+            evaluate_best(&[seven.clone(), vec![code("3D")]].concat()),
+            None
+        );
+        assert_eq!(evaluate([0, seven[1], seven[2], seven[3], seven[4]]), None);
+        assert_eq!(
+            evaluate([u32::MAX, seven[1], seven[2], seven[3], seven[4]]),
+            None
+        );
+    }
+
+    #[test]
+    fn there_are_7462_distinct_strengths() {
+        let tables = tables();
+        let max = tables
+            .flushes
+            .iter()
+            .chain(tables.unique5.iter())
+            .chain(tables.products.iter().map(|(_, s)| s))
+            .max();
+        assert_eq!(max, Some(&7462));
+        assert_eq!(tables.products.len(), 4888);
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(strength("7S 5H 4D 3C 2S"), 1);
+        assert_eq!(strength("AS KS QS JS 10S"), 7462);
+    }
+
+    #[test]
+    fn known_hands_are_ordered() {
+        let hands = [
+            "7S 5H 4D 3C 2S",
+            "AS KH QD JC 9S",
+            "2S 2H 3D 4C 5S",
+            "AS AH KD QC JS",
+            "3S 3H 2D 2C 4S",
+            "AS AH KD KC QS",
+            "2S 2H 2D 3C 4S",
+            "AS 2H 3D 4C 5S",
+            "10S JH QD KC AS",
+            "7H 5H 4H 3H 2H",
+            "AH KH QH JH 9H",
+            "2S 2H 2D 3C 3S",
+            "AS AH AD KC KS",
+            "2S 2H 2D 2C 3S",
+            "AS AH AD AC KS",
+            "AH 2H 3H 4H 5H",
+            "AS KS QS JS 10S",
+        ];
+        for pair in hands.windows(2) {
+            assert!(strength(pair[0]) < strength(pair[1]), "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn evaluate_best_picks_the_strongest_five() {
+        let seven: Vec<u32> = "AS KS 2D 7C QS JS 10S"
+            .split_whitespace()
+            .map(code)
+            .collect();
+        assert_eq!(evaluate_best(&seven), Some(7462));
+        assert_eq!(evaluate_best(&seven[..5]), Some(strength("AS KS 2D 7C QS")));
+    }
+
+    #[test]
+    fn suits_do_not_matter_without_a_flush() {
+        assert_eq!(strength("AS KH QD JC 9S"), strength("AD KC QH JS 9H"));
+        assert_eq!(strength("8S 8H 4D 4C 2S"), strength("8D 8C 4S 4H 2D"));
+    }
+
+    #[test]
+    fn agrees_with_score_on_every_five_card_hand() {
+        let deck = Card::deck();
+        let codes: Vec<u32> = deck.iter().filter_map(encode).collect();
+        let mut scores: HashMap<Strength, Score> = HashMap::new();
+
+        for a in 0..48 {
+            for b in a + 1..49 {
+                for c in b + 1..50 {
+                    for d in c + 1..51 {
+                        for e in d + 1..52 {
+                            let strength =
+                                super::strength([codes[a], codes[b], codes[c], codes[d], codes[e]]);
+                            let hand = vec![deck[a], deck[b], deck[c], deck[d], deck[e]];
+                            let score = Hand::from_cards(hand).score_hand();
+                            let seen = scores.entry(strength).or_insert_with(|| score.clone());
+                            assert_eq!(*seen, score);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut by_strength: Vec<_> = scores.into_iter().collect();
+        by_strength.sort_by_key(|(s, _)| *s);
+        assert_eq!(by_strength.len(), 7462);
+        for pair in by_strength.windows(2) {
+            assert!(pair[0].1 < pair[1].1, "{:?}", pair);
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::card::{Card, Rank, Suit};
+use crate::hand::Hand;
+use crate::score::Score;
+
+use std::cmp::Ordering;
+
+const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ranking {
+    /// Best high hand wins, aces play high or low in a straight.
+    #[default]
+    High,
+    /// Lowest hand wins, aces are low and straights and flushes don't count.
+    AceToFive,
+    /// Lowest hand wins, aces are high and straights and flushes count against you.
+    DeuceToSeven,
+}
+
+/// Why a hand can't be scored under a rule set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulesError {
+    /// The hand holds a joker, but jokers aren't wild.
+    JokerNotInPlay,
+}
+
+/// How hands are ranked and which cards are wild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RuleSet {
+    pub ranking: Ranking,
+    pub jokers_wild: bool,
+    pub deuces_wild: bool,
+}
+
+impl RuleSet {
+    pub const STANDARD: RuleSet = RuleSet {
+        ranking: Ranking::High,
+        jokers_wild: false,
+        deuces_wild: false,
+    };
+    pub const JOKERS_WILD: RuleSet = RuleSet {
+        jokers_wild: true,
+        ..RuleSet::STANDARD
+    };
+    pub const DEUCES_WILD: RuleSet = RuleSet {
+        deuces_wild: true,
+        ..RuleSet::STANDARD
+    };
+    pub const ACE_TO_FIVE: RuleSet = RuleSet {
+        ranking: Ranking::AceToFive,
+        ..RuleSet::STANDARD
+    };
+    pub const DEUCE_TO_SEVEN: RuleSet = RuleSet {
+        ranking: Ranking::DeuceToSeven,
+        ..RuleSet::STANDARD
+    };
+
+    /// The score of the hand under these rules, with every wild card standing in for
+    /// whichever card makes the best hand. In the low rankings that is the lowest score.
+    pub fn score(&self, cards: &[Card]) -> Result<Score, RulesError> {
+        if !self.jokers_wild && cards.iter().any(Card::is_joker) {
+            return Err(RulesError::JokerNotInPlay);
+        }
+
+        let (wilds, naturals): (Vec<Card>, Vec<Card>) = cards.iter().partition(|c| self.is_wild(c));
+        if wilds.is_empty() {
+            return Ok(self.score_naturals(naturals));
+        }
+
+        let flush_suit = match naturals.first() {
+            Some(first) if naturals.iter().all(|c| c.suit == first.suit) => first.suit,
+            Some(_) => return Ok(self.best(self.fill_wilds(&naturals, wilds.len(), None))),
+            None => Suit::Heart,
+        };
+        let mut candidates = self.fill_wilds(&naturals, wilds.len(), Some(flush_suit));
+        candidates.extend(self.fill_wilds(&naturals, wilds.len(), None));
+        Ok(self.best(candidates))
+    }
+
+    /// Orders two hands so that the one that wins under these rules is `Greater`.
+    pub fn compare(&self, a: &[Card], b: &[Card]) -> Result<Ordering, RulesError> {
+        Ok(self.compare_scores(&self.score(a)?, &self.score(b)?))
+    }
+
+    /// Orders two scores so that the one that wins under these rules is `Greater`.
+    pub fn compare_scores(&self, a: &Score, b: &Score) -> Ordering {
+        match self.ranking {
+            Ranking::High => a.cmp(b),
+            Ranking::AceToFive | Ranking::DeuceToSeven => b.cmp(a),
+        }
+    }
+
+    fn is_wild(&self, card: &Card) -> bool {
+        card.is_joker() || (self.deuces_wild && card.rank == 2)
+    }
+
+    fn score_naturals(&self, mut cards: Vec<Card>) -> Score {
+        match self.ranking {
+            Ranking::High => Hand::from_cards(cards).score_hand(),
+            Ranking::AceToFive => {
+                for card in cards.iter_mut().filter(|c| c.rank == 14) {
+                    card.rank = 1;
+                }
+                Hand::from_cards(cards).score_with(false, false)
+            }
+            Ranking::DeuceToSeven => Hand::from_cards(cards).score_with(true, false),
+        }
+    }
+
+    fn best(&self, candidates: Vec<Vec<Card>>) -> Score {
+        candidates
+            .into_iter()
+            .map(|cards| self.score_naturals(cards))
+            .max_by(|a, b| self.compare_scores(a, b))
+            .expect("at least one way to fill the wild cards")
+    }
+
+    /// Every hand the wild cards could make. Since wild cards are interchangeable only
+    /// the multiset of ranks they take matters. With `Some(suit)` they all take that suit,
+    /// otherwise they are dealt suits that can't make a flush.
+    fn fill_wilds(&self, naturals: &[Card], wilds: usize, suit: Option<Suit>) -> Vec<Vec<Card>> {
+        let suits: Vec<Suit> = match suit {
+            Some(suit) => vec![suit],
+            None => {
+                let avoid = naturals.first().map_or(Suit::Heart, |c| c.suit);
+                SUITS.iter().copied().filter(|&s| s != avoid).collect()
+            }
+        };
+
+        rank_multisets(wilds)
+            .into_iter()
+            .map(|ranks| {
+                let mut hand = naturals.to_vec();
+                hand.extend(ranks.iter().enumerate().map(|(i, &rank)| Card {
+                    rank,
+                    suit: suits[i % suits.len()],
+                }));
+                hand
+            })
+            .collect()
+    }
+}
+
+/// Every multiset of `n` ranks from deuce to ace.
+fn rank_multisets(n: usize) -> Vec<Vec<Rank>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    rank_multisets(n - 1)
+        .into_iter()
+        .flat_map(|ranks| {
+            let from = ranks.last().copied().unwrap_or(2);
+            (from..=14).map(move |r| {
+                let mut more = ranks.clone();
+                more.push(r);
+                more
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.split_whitespace().map(Card::from_str).collect()
+    }
+
+    fn score(rules: RuleSet, hand: &str) -> Score {
+        rules.score(&cards(hand)).unwrap()
+    }
+
+    fn beats(rules: RuleSet, a: &str, b: &str) -> bool {
+        rules.compare(&cards(a), &cards(b)) == Ok(Ordering::Greater)
+    }
+
+    #[test]
+    fn rank_multisets_count() {
+        assert_eq!(rank_multisets(1).len(), 13);
+        assert_eq!(rank_multisets(2).len(), 91);
+        assert_eq!(rank_multisets(4).len(), 1820);
+    }
+
+    #[test]
+    fn default_is_standard() {
+        assert_eq!(RuleSet::default(), RuleSet::STANDARD);
+        for hand in ["2S 3S 4S 5S AS", "KH KD 7C 7S 2D", "4H 9C JD QS AH"].iter() {
+            assert_eq!(
+                score(RuleSet::default(), hand),
+                Hand::from_cards(cards(hand)).score_hand()
+            );
+        }
+    }
+
+    #[test]
+    fn joker_needs_jokers_wild() {
+        let hand = cards("JK AS AH AD AC");
+        assert_eq!(
+            RuleSet::STANDARD.score(&hand),
+            Err(RulesError::JokerNotInPlay)
+        );
+        assert_eq!(
+            RuleSet::DEUCES_WILD.compare(&cards("AS KS QS JS 10S"), &hand),
+            Err(RulesError::JokerNotInPlay)
+        );
+        assert_eq!(
+            crate::winning_hands_with(&["JK AS AH AD AC", "2S 2H 3D 4C 5S"], &RuleSet::ACE_TO_FIVE),
+            Err(RulesError::JokerNotInPlay)
+        );
+    }
+
+    #[test]
+    fn joker_makes_five_of_a_kind() {
+        assert_eq!(
+            score(RuleSet::JOKERS_WILD, "JK AS AH AD AC"),
+            Score::FiveOfAKind(14)
+        );
+        assert!(beats(
+            RuleSet::JOKERS_WILD,
+            "JK AS AH AD AC",
+            "AS KS QS JS 10S"
+        ));
+    }
+
+    #[test]
+    fn joker_completes_straight_flush() {
+        assert_eq!(
+            score(RuleSet::JOKERS_WILD, "JK 9H 10H JH QH"),
+            Score::StraightFlush(13)
+        );
+    }
+
+    #[test]
+    fn joker_completes_flush() {
+        assert_eq!(
+            score(RuleSet::JOKERS_WILD, "JK 2H 5H 9H KH"),
+            Score::Flush(vec![14, 13, 9, 5, 2])
+        );
+    }
+
+    #[test]
+    fn joker_fills_a_gutshot() {
+        assert_eq!(
+            score(RuleSet::JOKERS_WILD, "JK 5C 6D 8S 9H"),
+            Score::Straight(9)
+        );
+    }
+
+    #[test]
+    fn deuces_wild() {
+        assert_eq!(
+            score(RuleSet::DEUCES_WILD, "2C 2D 7S 7H KD"),
+            Score::FourOfAKind(7, 13)
+        );
+        assert_eq!(
+            score(RuleSet::DEUCES_WILD, "2C 2D 2H 2S AS"),
+            Score::FiveOfAKind(14)
+        );
+        assert_eq!(
+            score(RuleSet::STANDARD, "2C 2D 7S 7H KD"),
+            Score::TwoPair(7, 2, 13)
+        );
+    }
+
+    #[test]
+    fn five_wild_cards() {
+        let rules = RuleSet {
+            jokers_wild: true,
+            ..RuleSet::DEUCES_WILD
+        };
+        assert_eq!(score(rules, "JK 2C 2D 2H 2S"), Score::FiveOfAKind(14));
+    }
+
+    #[test]
+    fn ace_to_five_wheel_is_the_best_hand() {
+        let rules = RuleSet::ACE_TO_FIVE;
+        assert_eq!(
+            score(rules, "AS 2D 3H 4C 5S"),
+            Score::HighCard(vec![5, 4, 3, 2, 1])
+        );
+        assert!(beats(rules, "AS 2D 3H 4C 5S", "AS 2S 3S 4S 6S"));
+        assert!(beats(rules, "5S 4D 3H 2C AH", "6S 4D 3H 2C AH"));
+    }
+
+    #[test]
+    fn ace_to_five_ignores_flushes_and_pairs_lose() {
+        let rules = RuleSet::ACE_TO_FIVE;
+        assert_eq!(
+            score(rules, "2H 4H 6H 8H 9H"),
+            Score::HighCard(vec![9, 8, 6, 4, 2])
+        );
+        assert!(beats(rules, "KS QD JH 10C 9S", "AS AD 2H 3C 4S"));
+    }
+
+    #[test]
+    fn ace_to_five_joker_plays_lowest() {
+        let rules = RuleSet {
+            jokers_wild: true,
+            ..RuleSet::ACE_TO_FIVE
+        };
+        assert_eq!(
+            score(rules, "JK 2S 3D 4H 5C"),
+            Score::HighCard(vec![5, 4, 3, 2, 1])
+        );
+        assert_eq!(
+            score(rules, "JK AS 2D 3H 4C"),
+            Score::HighCard(vec![5, 4, 3, 2, 1])
+        );
+    }
+
+    #[test]
+    fn deuce_to_seven() {
+        let rules = RuleSet::DEUCE_TO_SEVEN;
+        assert_eq!(
+            score(rules, "AS 2D 3H 4C 5S"),
+            Score::HighCard(vec![14, 5, 4, 3, 2])
+        );
+        assert!(beats(rules, "7S 5D 4H 3C 2S", "7S 6D 4H 3C 2S"));
+        assert!(beats(rules, "KS QD JH 9C 8S", "AS 2D 3H 4C 5S"));
+        assert!(beats(rules, "8S 6D 5H 4C 3S", "3S 4D 5H 6C 7S"));
+        assert!(beats(rules, "8S 7D 5H 4C 3S", "7H 5H 4H 3H 2H"));
+    }
+
+    #[test]
+    fn winning_hands_with_low_rules() {
+        let hands = ["AS 2D 3H 4C 5S", "7S 5D 4H 3C 2S", "KS KD 7H 7C 2S"];
+        assert_eq!(
+            crate::winning_hands_with(&hands, &RuleSet::ACE_TO_FIVE),
+            Ok(Some(vec!["AS 2D 3H 4C 5S"]))
+        );
+        assert_eq!(
+            crate::winning_hands_with(&hands, &RuleSet::DEUCE_TO_SEVEN),
+            Ok(Some(vec!["7S 5D 4H 3C 2S"]))
+        );
+        assert_eq!(
+            crate::winning_hands_with(&hands, &RuleSet::DEUCES_WILD),
+            Ok(Some(vec!["KS KD 7H 7C 2S"]))
+        );
+    }
+}
@@ -1,18 +1,104 @@
 use crate::card::Rank;
 
+use std::fmt;
+
 type Kicker = u8;
 
-#[derive(Debug, Ord, Eq, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, Ord, Eq, PartialOrd, PartialEq)]
 pub enum Score {
-    HighCard(Vec<Rank>), // must be sorted high to low
-    OnePair(Rank),
-    TwoPair(Rank, Rank, Kicker), // ranks must be ordered high to low
+    HighCard(Vec<Rank>),                // must be sorted high to low
+    OnePair(Rank, Vec<Kicker>),         // kickers must be sorted high to low
+    TwoPair(Rank, Rank, Kicker),        // ranks must be ordered high to low
     ThreeOfAKind(Rank, Kicker, Kicker), //kickers must be sorted high to low
     Straight(Rank),
     Flush(Vec<Rank>), // must be sorted high to low
     FullHouse(Rank, Kicker),
     FourOfAKind(Rank, Kicker),
     StraightFlush(Rank),
+    FiveOfAKind(Rank), // only possible with wild cards or several decks
+}
+
+/// Describes the hand in words, like "Two Pair, Kings and Sixes, Four kicker".
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Score::HighCard(ranks) => write!(f, "High Card, {}", names(ranks)),
+            Score::OnePair(pair, kickers) => {
+                write!(f, "One Pair, {}, {}", plural(*pair), kicker_names(kickers))
+            }
+            Score::TwoPair(high, low, kicker) => write!(
+                f,
+                "Two Pair, {} and {}, {}",
+                plural(*high),
+                plural(*low),
+                kicker_names(&[*kicker])
+            ),
+            Score::ThreeOfAKind(rank, k1, k2) => write!(
+                f,
+                "Three of a Kind, {}, {}",
+                plural(*rank),
+                kicker_names(&[*k1, *k2])
+            ),
+            Score::Straight(high) => write!(f, "Straight, {} high", name(*high)),
+            Score::Flush(ranks) => write!(f, "Flush, {}", names(ranks)),
+            Score::FullHouse(three, pair) => write!(
+                f,
+                "Full House, {} full of {}",
+                plural(*three),
+                plural(*pair)
+            ),
+            Score::FourOfAKind(rank, kicker) => write!(
+                f,
+                "Four of a Kind, {}, {}",
+                plural(*rank),
+                kicker_names(&[*kicker])
+            ),
+            Score::StraightFlush(14) => write!(f, "Royal Flush"),
+            Score::StraightFlush(high) => write!(f, "Straight Flush, {} high", name(*high)),
+            Score::FiveOfAKind(rank) => write!(f, "Five of a Kind, {}", plural(*rank)),
+        }
+    }
+}
+
+fn name(rank: Rank) -> &'static str {
+    match rank {
+        1 | 14 => "Ace",
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Joker",
+    }
+}
+
+fn plural(rank: Rank) -> String {
+    match rank {
+        6 => "Sixes".to_string(),
+        r => format!("{}s", name(r)),
+    }
+}
+
+fn names(ranks: &[Rank]) -> String {
+    ranks
+        .iter()
+        .map(|&r| name(r))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn kicker_names(kickers: &[Kicker]) -> String {
+    match kickers {
+        [kicker] => format!("{} kicker", name(*kicker)),
+        kickers => format!("{} kickers", names(kickers)),
+    }
 }
 
 #[cfg(test)]
@@ -21,7 +107,7 @@ mod tests {
 
     #[test]
     fn tuple_cmp_is_lexicographical() {
-        assert!(Score::TwoPair(10, 5, 3) > Score::OnePair(13));
+        assert!(Score::TwoPair(10, 5, 3) > Score::OnePair(13, vec![12, 11, 9]));
         assert!(Score::TwoPair(10, 5, 4) > Score::TwoPair(10, 5, 3));
         assert!(Score::TwoPair(10, 6, 4) > Score::TwoPair(10, 5, 4));
         assert!(Score::TwoPair(13, 6, 4) > Score::TwoPair(10, 6, 4));
@@ -38,4 +124,45 @@ mod tests {
         assert!(Score::ThreeOfAKind(13, 7, 4) > Score::ThreeOfAKind(13, 6, 4));
         assert!(Score::ThreeOfAKind(13, 6, 5) > Score::ThreeOfAKind(13, 6, 4));
     }
+
+    #[test]
+    fn one_pair_kickers_cascade() {
+        assert!(Score::OnePair(8, vec![13, 6, 2]) > Score::OnePair(8, vec![13, 5, 4]));
+        assert!(Score::OnePair(9, vec![4, 3, 2]) > Score::OnePair(8, vec![14, 13, 12]));
+    }
+
+    #[test]
+    fn display_describes_the_hand() {
+        let test = |score: Score, s: &str| assert_eq!(score.to_string(), s);
+
+        test(
+            Score::HighCard(vec![14, 13, 9, 7, 4]),
+            "High Card, Ace, King, Nine, Seven, Four",
+        );
+        test(
+            Score::OnePair(10, vec![12, 6, 2]),
+            "One Pair, Tens, Queen, Six, Two kickers",
+        );
+        test(
+            Score::TwoPair(13, 6, 4),
+            "Two Pair, Kings and Sixes, Four kicker",
+        );
+        test(
+            Score::ThreeOfAKind(7, 14, 3),
+            "Three of a Kind, Sevens, Ace, Three kickers",
+        );
+        test(Score::Straight(5), "Straight, Five high");
+        test(
+            Score::Flush(vec![14, 11, 8, 5, 3]),
+            "Flush, Ace, Jack, Eight, Five, Three",
+        );
+        test(Score::FullHouse(13, 7), "Full House, Kings full of Sevens");
+        test(
+            Score::FourOfAKind(9, 14),
+            "Four of a Kind, Nines, Ace kicker",
+        );
+        test(Score::StraightFlush(9), "Straight Flush, Nine high");
+        test(Score::StraightFlush(14), "Royal Flush");
+        test(Score::FiveOfAKind(12), "Five of a Kind, Queens");
+    }
 }
@@ -14,7 +14,7 @@ fn hs_from<'a>(input: &[&'a str]) -> HashSet<&'a str> {
 ///
 /// Note that the output can be in any order. Here, we use a HashSet to
 /// abstract away the order of outputs.
-fn test<'a, 'b>(input: &[&'a str], expected: &[&'b str]) {
+fn test(input: &[&str], expected: &[&str]) {
     assert_eq!(
         hs_from(&winning_hands(input).expect("This test should produce Some value",)),
         hs_from(expected)
@@ -64,6 +64,22 @@ fn test_highest_pair_wins() {
     test(&["4S 2H 6S 2D JH", "2S 4H 6C 4D JD"], &["2S 4H 6C 4D JD"])
 }
 
+#[test]
+fn test_one_pair_cascade() {
+    // both hands have the same pair, tie goes to the highest kicker, down to the last
+    // one. Pairs used to tie on their rank alone, so both of these hands won.
+    test(&["4H 4S AH JC 3D", "4C 4D AS 5D 6C"], &["4H 4S AH JC 3D"]);
+    test(&["4H 4S AH JC 3D", "4C 4D AS JD 2C"], &["4H 4S AH JC 3D"]);
+}
+
+#[test]
+fn test_one_pair_with_the_same_kickers_ties() {
+    test(
+        &["4H 4S AH JC 3D", "4C 4D AS JD 3C"],
+        &["4H 4S AH JC 3D", "4C 4D AS JD 3C"],
+    )
+}
+
 #[test]
 fn test_two_pairs_beats_one_pair() {
     test(&["2S 8H 6S 8D JH", "4S 5H 4C 8C 5C"], &["4S 5H 4C 8C 5C"])
@@ -5,6 +5,16 @@
 //! An advantage of this method is that its more re-usable and extensible. Should the format of the
 //! instructions change all that would need to change is my Instructions trait
 
+mod parse;
+mod plan;
+mod trace;
+mod world;
+
+pub use parse::{ParseError, MAX_DEPTH, MAX_INSTRUCTIONS};
+pub use plan::plan;
+pub use trace::Trajectory;
+pub use world::{BlockedPolicy, Blocker, Collision, RunError, World};
+
 /// Converts a string of encoded instructions into an iterator of encoded instructions
 pub trait Instructions: AsRef<str> {
     // Because I wanted to try out dynamic dispatch and didn't want to return a vec
@@ -18,18 +28,35 @@ pub trait Instructions: AsRef<str> {
 
         Box::new(iter)
     }
+
+    /// Like [decode_instructions](Instructions::decode_instructions), but reports where the
+    /// first bad character is instead of panicking
+    fn try_decode_instructions(&self) -> Result<Vec<Instruction>, ParseError> {
+        parse::parse_plain(self.as_ref())
+    }
+
+    /// Decodes the extended language, with `U`-turns, `B`ackward steps, counts like `3A`
+    /// and groups like `2(AR)`
+    fn decode_extended(&self) -> Result<Vec<Instruction>, ParseError> {
+        parse::parse_extended(self.as_ref())
+    }
 }
 impl<T: AsRef<str>> Instructions for T {}
 
 /// Represents the possible instructions a robot can do
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Instruction {
     TurnLeft,
     TurnRight,
     Advance,
+    /// Turn to face the opposite way
+    UTurn,
+    /// Step back 1 position without turning
+    Backward,
 }
 
 /// Represents the directions the robot can face
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum Direction {
     North,
     East,
@@ -57,15 +84,58 @@ impl Direction {
             Direction::West => Direction::North,
         }
     }
+
+    /// Get the opposite direction of the current dir.
+    fn reverse(&self) -> Direction {
+        self.left().left()
+    }
 }
 
 // Just to group related data
 /// X and Y coordinates
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 struct Vec2 {
     x: i32,
     y: i32,
 }
 
+impl Vec2 {
+    /// The position 1 step in the given direction
+    fn step(self, dir: Direction) -> Vec2 {
+        let Vec2 { x, y } = self;
+        match dir {
+            Direction::North => Vec2 { x, y: y + 1 },
+            Direction::East => Vec2 { x: x + 1, y },
+            Direction::South => Vec2 { x, y: y - 1 },
+            Direction::West => Vec2 { x: x - 1, y },
+        }
+    }
+
+    /// Like [step](Vec2::step), but `None` off the edge of the coordinates
+    fn checked_step(self, dir: Direction) -> Option<Vec2> {
+        let Vec2 { x, y } = self;
+        Some(match dir {
+            Direction::North => Vec2 {
+                x,
+                y: y.checked_add(1)?,
+            },
+            Direction::East => Vec2 {
+                x: x.checked_add(1)?,
+                y,
+            },
+            Direction::South => Vec2 {
+                x,
+                y: y.checked_sub(1)?,
+            },
+            Direction::West => Vec2 {
+                x: x.checked_sub(1)?,
+                y,
+            },
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Robot {
     pos: Vec2,
     dir: Direction,
@@ -96,27 +166,60 @@ impl Robot {
     }
 
     /// Advance the robot 1 position in the direction it is facing
-    pub fn advance(mut self) -> Self {
-        match self.dir {
-            Direction::North => self.pos.y += 1,
-            Direction::East => self.pos.x += 1,
-            Direction::South => self.pos.y -= 1,
-            Direction::West => self.pos.x -= 1,
-        };
-        self
+    pub fn advance(self) -> Self {
+        Self {
+            pos: self.pos.step(self.dir),
+            ..self
+        }
+    }
+
+    /// Robot turn around
+    pub fn u_turn(self) -> Self {
+        Self {
+            dir: self.dir.reverse(),
+            ..self
+        }
+    }
+
+    /// Move the robot 1 position back, still facing the same way
+    pub fn backward(self) -> Self {
+        Self {
+            pos: self.pos.step(self.dir.reverse()),
+            ..self
+        }
+    }
+
+    /// Apply a single instruction to the robot
+    pub fn apply(self, instruction: Instruction) -> Self {
+        match instruction {
+            Instruction::TurnRight => self.turn_right(),
+            Instruction::TurnLeft => self.turn_left(),
+            Instruction::Advance => self.advance(),
+            Instruction::UTurn => self.u_turn(),
+            Instruction::Backward => self.backward(),
+        }
     }
 
     // Changed the api to AsRef<str> because it's a more ergonomic
     /// Apply a string of instructions to the robot.
     /// Instructions are "LRA", turn left, right and advance respectively.
     pub fn instructions(self, instructions: impl AsRef<str>) -> Self {
-        instructions
-            .decode_instructions()
-            .fold(self, |acc, ins| match ins {
-                Instruction::TurnRight => acc.turn_right(),
-                Instruction::TurnLeft => acc.turn_left(),
-                Instruction::Advance => acc.advance(),
-            })
+        instructions.decode_instructions().fold(self, Robot::apply)
+    }
+
+    /// Apply a string of instructions to the robot, recording every pose on the way
+    pub fn trace(self, instructions: impl AsRef<str>) -> Trajectory {
+        Trajectory::record(self, instructions.decode_instructions())
+    }
+
+    /// Apply a string in the extended instruction language, or get back where it
+    /// stops making sense. Plain "LRA" strings do the same as in
+    /// [instructions](Robot::instructions).
+    pub fn try_instructions(self, instructions: impl AsRef<str>) -> Result<Self, ParseError> {
+        Ok(instructions
+            .decode_extended()?
+            .into_iter()
+            .fold(self, Robot::apply))
     }
 
     /// Get the position (x, y) coordinates of the robot
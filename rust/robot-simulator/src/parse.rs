@@ -0,0 +1,274 @@
+//! The extended instruction language. On top of `L`, `R` and `A` it has `U` to turn
+//! around, `B` to step backwards, repeat counts like `3A` and repeated groups like `2(AR)`.
+//! Groups can be nested, and a plain string of `LRA` means the same thing in both languages.
+
+use crate::Instruction;
+
+/// The most instructions a string may expand to, so a short string like `99999999A`
+/// can't run the process out of memory
+pub const MAX_INSTRUCTIONS: usize = 1 << 20;
+
+/// How deeply groups can nest, so a string of `(`s can't overflow the stack
+pub const MAX_DEPTH: usize = 64;
+
+/// Where an instruction string went wrong. Positions are char indices from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// A character that isn't an instruction, count or bracket
+    InvalidCharacter { position: usize, found: char },
+    /// A `(` without a matching `)`
+    UnclosedGroup { position: usize },
+    /// A `)` without a matching `(`
+    UnopenedGroup { position: usize },
+    /// A count that isn't followed by anything to repeat, or that would expand the
+    /// instructions past [MAX_INSTRUCTIONS](MAX_INSTRUCTIONS)
+    InvalidCount { position: usize },
+    /// A `(` nested more than [MAX_DEPTH](MAX_DEPTH) deep
+    TooDeep { position: usize },
+}
+
+impl ParseError {
+    pub fn position(&self) -> usize {
+        match *self {
+            ParseError::InvalidCharacter { position, .. }
+            | ParseError::UnclosedGroup { position }
+            | ParseError::UnopenedGroup { position }
+            | ParseError::InvalidCount { position }
+            | ParseError::TooDeep { position } => position,
+        }
+    }
+}
+
+/// Decodes `L`, `R` and `A` only
+/// O(n)
+pub(crate) fn parse_plain(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    s.chars()
+        .enumerate()
+        .map(|(position, c)| match c {
+            'L' => Ok(Instruction::TurnLeft),
+            'R' => Ok(Instruction::TurnRight),
+            'A' => Ok(Instruction::Advance),
+            found => Err(ParseError::InvalidCharacter { position, found }),
+        })
+        .collect()
+}
+
+/// Decodes the extended language, expanding every repeat
+/// O(n) in the length of the expanded instructions, which is at most [MAX_INSTRUCTIONS]
+pub(crate) fn parse_extended(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut parser = Parser {
+        chars,
+        pos: 0,
+        depth: 0,
+    };
+    let instructions = parser.sequence()?;
+    match parser.peek() {
+        Some(')') => Err(ParseError::UnopenedGroup {
+            position: parser.pos,
+        }),
+        _ => Ok(instructions),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Groups open around the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Items up to the end of input or a `)`, which is left for the caller
+    fn sequence(&mut self) -> Result<Vec<Instruction>, ParseError> {
+        let mut instructions = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == ')' {
+                break;
+            }
+            let start = self.pos;
+            let item = self.item()?;
+            if instructions.len() + item.len() > MAX_INSTRUCTIONS {
+                return Err(ParseError::InvalidCount { position: start });
+            }
+            instructions.extend(item);
+        }
+        Ok(instructions)
+    }
+
+    /// An optional count followed by an instruction or a group
+    fn item(&mut self) -> Result<Vec<Instruction>, ParseError> {
+        let start = self.pos;
+        let count = self.count()?;
+        let invalid_count = || ParseError::InvalidCount { position: start };
+
+        let body = match self.peek() {
+            Some('(') => {
+                let open = self.pos;
+                if self.depth == MAX_DEPTH {
+                    return Err(ParseError::TooDeep { position: open });
+                }
+                self.pos += 1;
+                self.depth += 1;
+                let body = self.sequence()?;
+                self.depth -= 1;
+                if self.peek() != Some(')') {
+                    return Err(ParseError::UnclosedGroup { position: open });
+                }
+                self.pos += 1;
+                body
+            }
+            Some(c) => {
+                let ins = match c {
+                    'L' => Instruction::TurnLeft,
+                    'R' => Instruction::TurnRight,
+                    'A' => Instruction::Advance,
+                    'U' => Instruction::UTurn,
+                    'B' => Instruction::Backward,
+                    ')' if count.is_some() => return Err(invalid_count()),
+                    found => {
+                        return Err(ParseError::InvalidCharacter {
+                            position: self.pos,
+                            found,
+                        })
+                    }
+                };
+                self.pos += 1;
+                vec![ins]
+            }
+            // only reached after a count, since callers stop at the end of input
+            None => return Err(invalid_count()),
+        };
+
+        let count = count.unwrap_or(1);
+        body.len()
+            .checked_mul(count)
+            .filter(|&len| len <= MAX_INSTRUCTIONS)
+            .ok_or_else(invalid_count)?;
+        Ok(body.repeat(count))
+    }
+
+    fn count(&mut self) -> Result<Option<usize>, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map(Some)
+            .map_err(|_| ParseError::InvalidCount { position: start })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Instruction::*;
+
+    #[test]
+    fn plain_strings_parse_the_same_in_both() {
+        let s = "RRAAALAL";
+        assert_eq!(parse_plain(s), parse_extended(s));
+        assert_eq!(parse_plain(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn plain_errors_have_positions() {
+        assert_eq!(
+            parse_plain("LRAX"),
+            Err(ParseError::InvalidCharacter {
+                position: 3,
+                found: 'X'
+            })
+        );
+        assert_eq!(parse_plain("AU").unwrap_err().position(), 1);
+    }
+
+    #[test]
+    fn counts_and_groups_expand() {
+        assert_eq!(parse_extended("3A"), Ok(vec![Advance; 3]));
+        assert_eq!(
+            parse_extended("2(AR)L"),
+            Ok(vec![Advance, TurnRight, Advance, TurnRight, TurnLeft])
+        );
+        assert_eq!(
+            parse_extended("2(U2B)"),
+            Ok(vec![UTurn, Backward, Backward, UTurn, Backward, Backward])
+        );
+        assert_eq!(parse_extended("2(2(A))").unwrap().len(), 4);
+        assert_eq!(parse_extended("0A()"), Ok(vec![]));
+        assert_eq!(parse_extended("12A").unwrap().len(), 12);
+    }
+
+    #[test]
+    fn extended_errors_have_positions() {
+        assert_eq!(
+            parse_extended("A2(AX)"),
+            Err(ParseError::InvalidCharacter {
+                position: 4,
+                found: 'X'
+            })
+        );
+        assert_eq!(
+            parse_extended("A(2(A)"),
+            Err(ParseError::UnclosedGroup { position: 1 })
+        );
+        assert_eq!(
+            parse_extended("AR)A"),
+            Err(ParseError::UnopenedGroup { position: 2 })
+        );
+        assert_eq!(
+            parse_extended("A3"),
+            Err(ParseError::InvalidCount { position: 1 })
+        );
+        assert_eq!(
+            parse_extended("2(A3)"),
+            Err(ParseError::InvalidCount { position: 3 })
+        );
+        assert_eq!(
+            parse_extended("99999999999999999999999A"),
+            Err(ParseError::InvalidCount { position: 0 })
+        );
+    }
+
+    #[test]
+    fn nesting_is_capped() {
+        assert_eq!(
+            parse_extended(&"(".repeat(1_000_000)),
+            Err(ParseError::TooDeep {
+                position: MAX_DEPTH
+            })
+        );
+        let deepest = format!("{}A{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(parse_extended(&deepest), Ok(vec![Advance]));
+    }
+
+    #[test]
+    fn expansion_is_capped() {
+        assert_eq!(
+            parse_extended("99999999999999A"),
+            Err(ParseError::InvalidCount { position: 0 })
+        );
+        assert_eq!(
+            parse_extended("9999(9999(9999(A)))"),
+            Err(ParseError::InvalidCount { position: 5 })
+        );
+        let limit = format!("{MAX_INSTRUCTIONS}A");
+        assert_eq!(parse_extended(&limit).unwrap().len(), MAX_INSTRUCTIONS);
+        assert_eq!(
+            parse_extended(&format!("{limit}A")),
+            Err(ParseError::InvalidCount {
+                position: limit.len()
+            })
+        );
+    }
+}
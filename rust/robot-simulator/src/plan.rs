@@ -0,0 +1,306 @@
+//! Finding the shortest instruction string between two places.
+
+use crate::{Direction, Instruction, Robot, Vec2};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// The shortest string of `L`, `R` and `A` that takes `start` to `goal` without entering
+/// a `blocked` cell, facing any direction at the end. Turns count as a step, the same as
+/// advancing. `None` if the goal can't be reached.
+///
+/// An A* search over positions and headings, guided by the distance to the goal plus the
+/// turns needed to face it, so open ground costs about one pose per step of the answer.
+/// Blocked cells can only fence in a finite area, so before searching, the free cells
+/// around the goal are flooded to see whether it is fenced off from the start.
+/// O(d + b²) poses for a path of length d round b blocked cells
+pub fn plan(start: &Robot, goal: (i32, i32), blocked: &[(i32, i32)]) -> Option<String> {
+    let goal = Vec2 {
+        x: goal.0,
+        y: goal.1,
+    };
+    let blocked: HashSet<Vec2> = blocked.iter().map(|&(x, y)| Vec2 { x, y }).collect();
+    if blocked.contains(&goal) {
+        return None;
+    }
+    if let Some(region) = enclosure(goal, &blocked) {
+        if !region.contains(&start.pos) {
+            return None;
+        }
+    }
+
+    // The fewest steps found to each pose, and the instruction that last got there
+    let mut reached: HashMap<Robot, (u64, char)> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    reached.insert(*start, (0, ' '));
+    open.push(Open::new(*start, 0, goal));
+
+    while let Some(Open { robot, steps, .. }) = open.pop() {
+        if reached[&robot].0 < steps {
+            continue;
+        }
+        if robot.pos == goal {
+            return Some(path_to(robot, &reached));
+        }
+
+        for &(ins, c) in [
+            (Instruction::Advance, 'A'),
+            (Instruction::TurnLeft, 'L'),
+            (Instruction::TurnRight, 'R'),
+        ]
+        .iter()
+        {
+            let next = match ins {
+                Instruction::Advance => match robot.pos.checked_step(robot.dir) {
+                    Some(pos) => Robot { pos, ..robot },
+                    None => continue,
+                },
+                _ => robot.apply(ins),
+            };
+            if blocked.contains(&next.pos) {
+                continue;
+            }
+            if reached
+                .get(&next)
+                .is_some_and(|&(best, _)| best <= steps + 1)
+            {
+                continue;
+            }
+            reached.insert(next, (steps + 1, c));
+            open.push(Open::new(next, steps + 1, goal));
+        }
+    }
+    None
+}
+
+/// The instructions that led to `robot`, found by undoing each one back to the start
+fn path_to(mut robot: Robot, reached: &HashMap<Robot, (u64, char)>) -> String {
+    let mut path = Vec::new();
+    loop {
+        let c = reached[&robot].1;
+        robot = match c {
+            'A' => robot.backward(),
+            'L' => robot.turn_right(),
+            'R' => robot.turn_left(),
+            _ => break,
+        };
+        path.push(c);
+    }
+    path.iter().rev().collect()
+}
+
+/// A pose waiting to be searched from. The heap pops the lowest estimate of the whole
+/// path first, and of those the one furthest along, so ties run straight for the goal.
+struct Open {
+    estimate: u64,
+    steps: u64,
+    robot: Robot,
+}
+
+impl Open {
+    fn new(robot: Robot, steps: u64, goal: Vec2) -> Self {
+        Open {
+            estimate: steps + remaining(&robot, goal),
+            steps,
+            robot,
+        }
+    }
+
+    fn key(&self) -> (Reverse<u64>, u64) {
+        (Reverse(self.estimate), self.steps)
+    }
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The fewest steps from `robot` to `goal` on open ground: the distance along each axis,
+/// plus a turn if the goal is off to the side and two if it is behind
+fn remaining(robot: &Robot, goal: Vec2) -> u64 {
+    let dx = i64::from(goal.x) - i64::from(robot.pos.x);
+    let dy = i64::from(goal.y) - i64::from(robot.pos.y);
+    // how far the goal is ahead of the robot, and how far to one side
+    let (ahead, aside) = match robot.dir {
+        Direction::North => (dy, dx),
+        Direction::East => (dx, dy),
+        Direction::South => (-dy, dx),
+        Direction::West => (-dx, dy),
+    };
+    let turns = match (ahead >= 0, aside == 0) {
+        (true, true) => 0,
+        (true, false) => 1,
+        (false, _) => 2,
+    };
+    dx.unsigned_abs() + dy.unsigned_abs() + turns
+}
+
+/// The free cells connected to `from`, if `blocked` fences them into a finite area.
+/// However the cells are laid out, b of them can't fence in more than b² cells, so a
+/// flood that grows past that has found open ground and gives `None`.
+/// O(b²)
+fn enclosure(from: Vec2, blocked: &HashSet<Vec2>) -> Option<HashSet<Vec2>> {
+    let limit = blocked.len().saturating_mul(blocked.len());
+    let mut region = HashSet::from([from]);
+    let mut frontier = vec![from];
+    while let Some(cell) = frontier.pop() {
+        for dir in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            let next = match cell.checked_step(dir) {
+                Some(next) if !blocked.contains(&next) => next,
+                _ => continue,
+            };
+            if region.insert(next) {
+                if region.len() > limit {
+                    return None;
+                }
+                frontier.push(next);
+            }
+        }
+    }
+    Some(region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    fn test(start: Robot, goal: (i32, i32), blocked: &[(i32, i32)], expected_len: usize) {
+        let path = plan(&start, goal, blocked).unwrap();
+        assert_eq!(path.len(), expected_len, "{}", path);
+        let trace = start.trace(&path);
+        assert_eq!(trace.current().position(), goal);
+        assert!(trace.poses().all(|(pos, _)| !blocked.contains(&pos)));
+    }
+
+    #[test]
+    fn already_there() {
+        assert_eq!(
+            plan(&Robot::new(3, 3, Direction::West), (3, 3), &[]),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn open_ground() {
+        assert_eq!(
+            plan(&Robot::new(0, 0, Direction::North), (0, 3), &[]),
+            Some("AAA".to_string())
+        );
+        test(Robot::new(0, 0, Direction::North), (2, 3), &[], 6);
+        test(Robot::new(0, 0, Direction::South), (0, 2), &[], 4);
+        test(Robot::new(0, 0, Direction::East), (-1, -1), &[], 4);
+    }
+
+    #[test]
+    fn around_walls() {
+        // straight ahead is blocked, so go round with RALAALA or its mirror image
+        test(Robot::new(0, 0, Direction::North), (0, 2), &[(0, 1)], 7);
+        // a wall from (-2, 1) to (2, 1)
+        let wall: Vec<_> = (-2..=2).map(|x| (x, 1)).collect();
+        test(Robot::new(0, 0, Direction::North), (0, 2), &wall, 11);
+    }
+
+    #[test]
+    fn unreachable_goals() {
+        let walled_in = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+        assert_eq!(
+            plan(&Robot::new(5, 5, Direction::North), (0, 0), &walled_in),
+            None
+        );
+        assert_eq!(
+            plan(&Robot::new(0, 0, Direction::North), (5, 5), &walled_in),
+            None
+        );
+        assert_eq!(
+            plan(&Robot::new(0, 0, Direction::North), (1, 0), &walled_in),
+            None
+        );
+    }
+
+    #[test]
+    fn wide_detours() {
+        // a wall from (-100, 1) to (100, 1), so the way round is 100 cells to one side
+        let wall: Vec<_> = (-100..=100).map(|x| (x, 1)).collect();
+        test(
+            Robot::new(0, 0, Direction::North),
+            (0, 2),
+            &wall,
+            1 + 101 + 1 + 2 + 1 + 101,
+        );
+    }
+
+    #[test]
+    fn distant_goals() {
+        test(
+            Robot::new(0, 0, Direction::North),
+            (100_000, 100_000),
+            &[],
+            200_001,
+        );
+        let blocked = [(0, 1), (1, 0), (-1, 0)];
+        test(
+            Robot::new(0, 0, Direction::North),
+            (-100_000, -100_000),
+            &blocked,
+            200_003,
+        );
+    }
+
+    #[test]
+    fn distant_blocked_cells_do_not_widen_the_search() {
+        let mut blocked = vec![(1, 0), (-1, 0), (0, 1), (0, -1)];
+        blocked.push((i32::MAX, i32::MIN));
+        assert_eq!(
+            plan(&Robot::new(5, 5, Direction::North), (0, 0), &blocked),
+            None
+        );
+        test(Robot::new(5, 5, Direction::North), (3, 3), &blocked, 6);
+    }
+
+    #[test]
+    fn edges_of_the_grid() {
+        test(
+            Robot::new(i32::MIN, i32::MIN, Direction::South),
+            (i32::MIN, i32::MIN + 2),
+            &[],
+            4,
+        );
+        test(
+            Robot::new(i32::MAX, 0, Direction::East),
+            (i32::MAX - 1, 1),
+            &[],
+            4,
+        );
+        // walled into the corner, so the search runs into the edge of the coordinates
+        let walled_in = [(i32::MIN + 1, i32::MAX), (i32::MIN, i32::MAX - 1)];
+        assert_eq!(
+            plan(
+                &Robot::new(i32::MIN, i32::MAX, Direction::North),
+                (i32::MIN + 2, i32::MAX),
+                &walled_in
+            ),
+            None
+        );
+    }
+}
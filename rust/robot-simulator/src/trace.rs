@@ -0,0 +1,185 @@
+//! Recording a robot's path so long instruction streams can be replayed and drawn.
+
+use crate::{Direction, Instruction, Robot};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Every pose a robot took, with a cursor for stepping through them
+pub struct Trajectory {
+    robots: Vec<Robot>,
+    cursor: usize,
+}
+
+impl Trajectory {
+    /// Record the start pose and the pose after each instruction.
+    /// The cursor is left on the last one, where the robot ends up.
+    pub fn record(robot: Robot, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        let robots: Vec<Robot> = std::iter::once(robot)
+            .chain(instructions.into_iter().scan(robot, |robot, ins| {
+                *robot = robot.apply(ins);
+                Some(*robot)
+            }))
+            .collect();
+
+        Self {
+            cursor: robots.len() - 1,
+            robots,
+        }
+    }
+
+    /// Every `(position, Direction)` visited, start first
+    pub fn poses(&self) -> impl Iterator<Item = ((i32, i32), Direction)> + '_ {
+        self.robots.iter().map(|r| (r.position(), r.dir))
+    }
+
+    /// Number of poses, one more than the number of instructions
+    pub fn len(&self) -> usize {
+        self.robots.len()
+    }
+
+    /// Never true, since the start pose is always recorded
+    pub fn is_empty(&self) -> bool {
+        self.robots.is_empty()
+    }
+
+    /// Index of the current pose
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The robot as it was at the current pose
+    pub fn current(&self) -> Robot {
+        self.robots[self.cursor]
+    }
+
+    /// Move the cursor to the next pose, if there is one
+    pub fn step_forward(&mut self) -> Option<Robot> {
+        if self.cursor + 1 < self.robots.len() {
+            self.cursor += 1;
+            Some(self.current())
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the previous pose, if there is one
+    pub fn step_back(&mut self) -> Option<Robot> {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            Some(self.current())
+        } else {
+            None
+        }
+    }
+
+    /// Move the cursor to the start pose
+    pub fn rewind(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to pose `index`, or the last pose if there aren't that many
+    pub fn seek(&mut self, index: usize) -> Robot {
+        self.cursor = index.min(self.robots.len() - 1);
+        self.current()
+    }
+}
+
+/// Draws the path up to the cursor, north at the top. Each visited cell shows an arrow
+/// for the last heading the robot had there and every other cell is a `.`.
+impl fmt::Display for Trajectory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = &self.robots[..=self.cursor];
+        let mut cells = HashMap::new();
+        for robot in path {
+            cells.insert(robot.position(), arrow(robot.dir));
+        }
+
+        let xs = || path.iter().map(|r| r.pos.x);
+        let ys = || path.iter().map(|r| r.pos.y);
+        let (min_x, max_x) = (xs().min().unwrap(), xs().max().unwrap());
+        let (min_y, max_y) = (ys().min().unwrap(), ys().max().unwrap());
+
+        for y in (min_y..=max_y).rev() {
+            let row: String = (min_x..=max_x)
+                .map(|x| *cells.get(&(x, y)).unwrap_or(&'.'))
+                .collect();
+            write!(f, "{}", row)?;
+            if y != min_y {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn arrow(dir: Direction) -> char {
+    match dir {
+        Direction::North => '^',
+        Direction::East => '>',
+        Direction::South => 'v',
+        Direction::West => '<',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instructions;
+
+    fn trace(instructions: &str) -> Trajectory {
+        Robot::new(0, 0, Direction::North).trace(instructions)
+    }
+
+    #[test]
+    fn every_pose_is_recorded() {
+        let t = trace("AARA");
+        assert_eq!(t.len(), 5);
+        assert_eq!(
+            t.poses().collect::<Vec<_>>(),
+            vec![
+                ((0, 0), Direction::North),
+                ((0, 1), Direction::North),
+                ((0, 2), Direction::North),
+                ((0, 2), Direction::East),
+                ((1, 2), Direction::East),
+            ]
+        );
+        assert_eq!(
+            t.current(),
+            Robot::new(0, 0, Direction::North).instructions("AARA")
+        );
+    }
+
+    #[test]
+    fn stepping_works() {
+        let mut t = trace("AR");
+        assert_eq!(t.step_forward(), None);
+        assert_eq!(t.step_back(), Some(Robot::new(0, 1, Direction::North)));
+        assert_eq!(t.step_back(), Some(Robot::new(0, 0, Direction::North)));
+        assert_eq!(t.step_back(), None);
+        assert_eq!(t.cursor(), 0);
+        assert_eq!(t.step_forward(), Some(Robot::new(0, 1, Direction::North)));
+        assert_eq!(t.seek(10), Robot::new(0, 1, Direction::East));
+        t.rewind();
+        assert_eq!(t.current(), Robot::new(0, 0, Direction::North));
+    }
+
+    #[test]
+    fn rendering_works() {
+        let mut t = trace("AARALAALLAAA");
+        assert_eq!(t.to_string(), ".v\n.v\n>v\n^v\n^.");
+        t.seek(4);
+        assert_eq!(t.to_string(), ">>\n^.\n^.");
+        t.rewind();
+        assert_eq!(t.to_string(), "^");
+    }
+
+    #[test]
+    fn extended_instructions_can_be_recorded() {
+        let t = Trajectory::record(
+            Robot::new(-1, -1, Direction::East),
+            "2(AL)".decode_extended().unwrap(),
+        );
+        assert_eq!(t.to_string(), ".<\n>^");
+    }
+}
@@ -0,0 +1,265 @@
+//! A bounded grid that robots share with obstacles and each other. Unlike a lone
+//! [Robot](crate::Robot), a robot in a [World](World) can't walk through things, so every
+//! blocked move is reported back as a [Collision](Collision).
+
+use crate::{Instructions, ParseError, Robot, Vec2};
+use std::collections::HashSet;
+
+/// What happens to a robot whose advance is blocked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockedPolicy {
+    /// The robot stays put and ignores the rest of its instructions
+    Stop,
+    /// The robot stays put for that step and carries on with the next one
+    Skip,
+}
+
+/// The thing a robot ran into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blocker {
+    Wall,
+    Obstacle,
+    /// Another robot, by its id in the world
+    Robot(usize),
+}
+
+/// A blocked advance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    /// Index of the instruction that was blocked
+    pub step: usize,
+    /// The cell the robot tried to move into
+    pub position: (i32, i32),
+    pub blocker: Blocker,
+}
+
+/// Why a run didn't go cleanly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// The instructions don't parse, so the robot didn't move at all
+    Parse(ParseError),
+    /// Every collision on the way
+    Blocked(Vec<Collision>),
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+/// A `width` by `height` grid with its lower left cell at (0, 0)
+pub struct World {
+    width: i32,
+    height: i32,
+    policy: BlockedPolicy,
+    obstacles: HashSet<Vec2>,
+    robots: Vec<Robot>,
+}
+
+impl World {
+    pub fn new(width: i32, height: i32, policy: BlockedPolicy) -> Self {
+        Self {
+            width,
+            height,
+            policy,
+            obstacles: HashSet::new(),
+            robots: Vec::new(),
+        }
+    }
+
+    /// Block the cell (x, y)
+    pub fn with_obstacle(mut self, x: i32, y: i32) -> Self {
+        self.obstacles.insert(Vec2 { x, y });
+        self
+    }
+
+    /// Place a robot in the world and get back its id, or what is in the way
+    pub fn add_robot(&mut self, robot: Robot) -> Result<usize, Blocker> {
+        if let Some(blocker) = self.blocker_at(robot.pos) {
+            return Err(blocker);
+        }
+        self.robots.push(robot);
+        Ok(self.robots.len() - 1)
+    }
+
+    pub fn robot(&self, id: usize) -> Option<&Robot> {
+        self.robots.get(id)
+    }
+
+    pub fn robots(&self) -> &[Robot] {
+        &self.robots
+    }
+
+    /// Apply a string in the extended instruction language to one robot.
+    /// Returns every collision on the way, of which there is at most one under
+    /// [BlockedPolicy::Stop](BlockedPolicy::Stop). Steps count expanded instructions.
+    ///
+    /// Panics if there is no robot with that id.
+    pub fn run(&mut self, id: usize, instructions: impl AsRef<str>) -> Result<(), RunError> {
+        let mut collisions = Vec::new();
+        for (step, ins) in instructions.decode_extended()?.into_iter().enumerate() {
+            let robot = self.robots[id];
+            let next = robot.apply(ins);
+            if next.pos == robot.pos {
+                self.robots[id] = next;
+                continue;
+            }
+            match self.blocker_at(next.pos) {
+                None => self.robots[id] = next,
+                Some(blocker) => {
+                    collisions.push(Collision {
+                        step,
+                        position: (next.pos.x, next.pos.y),
+                        blocker,
+                    });
+                    if self.policy == BlockedPolicy::Stop {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if collisions.is_empty() {
+            Ok(())
+        } else {
+            Err(RunError::Blocked(collisions))
+        }
+    }
+
+    /// Run each robot's instructions in turn, in order of id. Robots without
+    /// instructions stay put.
+    pub fn run_all<S: AsRef<str>>(&mut self, instructions: &[S]) -> Vec<Result<(), RunError>> {
+        instructions
+            .iter()
+            .enumerate()
+            .take(self.robots.len())
+            .map(|(id, ins)| self.run(id, ins))
+            .collect()
+    }
+
+    /// O(n) in the number of robots
+    fn blocker_at(&self, pos: Vec2) -> Option<Blocker> {
+        if pos.x < 0 || pos.y < 0 || pos.x >= self.width || pos.y >= self.height {
+            Some(Blocker::Wall)
+        } else if self.obstacles.contains(&pos) {
+            Some(Blocker::Obstacle)
+        } else {
+            self.robots
+                .iter()
+                .position(|r| r.pos == pos)
+                .map(Blocker::Robot)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    fn world(policy: BlockedPolicy) -> World {
+        World::new(5, 5, policy).with_obstacle(2, 2)
+    }
+
+    #[test]
+    fn free_moves_work() {
+        let mut w = world(BlockedPolicy::Stop);
+        let id = w.add_robot(Robot::new(0, 0, Direction::North)).unwrap();
+        assert_eq!(w.run(id, "AAARAA"), Ok(()));
+        assert_eq!(w.robot(id).unwrap().position(), (2, 3));
+    }
+
+    #[test]
+    fn walls_stop_robots() {
+        let mut w = world(BlockedPolicy::Stop);
+        let id = w.add_robot(Robot::new(0, 0, Direction::South)).unwrap();
+        assert_eq!(
+            w.run(id, "ALA"),
+            Err(RunError::Blocked(vec![Collision {
+                step: 0,
+                position: (0, -1),
+                blocker: Blocker::Wall,
+            }]))
+        );
+        let robot = w.robot(id).unwrap();
+        assert_eq!(robot.position(), (0, 0));
+        assert_eq!(robot.direction(), &Direction::South);
+    }
+
+    #[test]
+    fn skipped_moves_carry_on() {
+        let mut w = world(BlockedPolicy::Skip);
+        let id = w.add_robot(Robot::new(2, 0, Direction::North)).unwrap();
+        assert_eq!(
+            w.run(id, "AAARA"),
+            Err(RunError::Blocked(vec![
+                Collision {
+                    step: 1,
+                    position: (2, 2),
+                    blocker: Blocker::Obstacle,
+                },
+                Collision {
+                    step: 2,
+                    position: (2, 2),
+                    blocker: Blocker::Obstacle,
+                },
+            ]))
+        );
+        assert_eq!(w.robot(id).unwrap().position(), (3, 1));
+    }
+
+    #[test]
+    fn robots_collide_with_each_other() {
+        let mut w = world(BlockedPolicy::Stop);
+        let a = w.add_robot(Robot::new(0, 0, Direction::East)).unwrap();
+        let b = w.add_robot(Robot::new(3, 0, Direction::West)).unwrap();
+        let results = w.run_all(&["A", "AA"]);
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(
+            results[1],
+            Err(RunError::Blocked(vec![Collision {
+                step: 1,
+                position: (1, 0),
+                blocker: Blocker::Robot(a),
+            }]))
+        );
+        assert_eq!(w.robot(b).unwrap().position(), (2, 0));
+    }
+
+    #[test]
+    fn robots_cannot_be_placed_on_taken_cells() {
+        let mut w = world(BlockedPolicy::Stop);
+        w.add_robot(Robot::new(1, 1, Direction::North)).unwrap();
+        assert_eq!(
+            w.add_robot(Robot::new(1, 1, Direction::East)).err(),
+            Some(Blocker::Robot(0))
+        );
+        assert_eq!(
+            w.add_robot(Robot::new(2, 2, Direction::East)).err(),
+            Some(Blocker::Obstacle)
+        );
+        assert_eq!(
+            w.add_robot(Robot::new(5, 0, Direction::East)).err(),
+            Some(Blocker::Wall)
+        );
+        assert_eq!(w.robots().len(), 1);
+    }
+
+    #[test]
+    fn bad_instructions_are_reported_before_moving() {
+        let mut w = world(BlockedPolicy::Stop);
+        let id = w.add_robot(Robot::new(0, 0, Direction::North)).unwrap();
+        assert_eq!(
+            w.run(id, "AAX"),
+            Err(RunError::Parse(ParseError::InvalidCharacter {
+                position: 2,
+                found: 'X'
+            }))
+        );
+        assert_eq!(w.robot(id).unwrap().position(), (0, 0));
+        assert_eq!(w.run(id, "2(AR)U"), Ok(()));
+        assert_eq!(w.robot(id).unwrap().direction(), &Direction::North);
+        assert_eq!(w.robot(id).unwrap().position(), (1, 1));
+    }
+}
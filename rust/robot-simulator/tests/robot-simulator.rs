@@ -124,3 +124,70 @@ fn follow_instructions_to_move_east_and_north() {
     assert_eq!((11, 5), robot.position());
     assert_eq!(&Direction::North, robot.direction());
 }
+
+#[test]
+fn u_turn_and_backward_work() {
+    let robot = Robot::new(0, 0, Direction::North).u_turn();
+    assert_eq!(&Direction::South, robot.direction());
+    let robot = robot.backward();
+    assert_eq!((0, 1), robot.position());
+    assert_eq!(&Direction::South, robot.direction());
+}
+
+#[test]
+fn extended_instructions_match_plain_ones() {
+    let plain = Robot::new(8, 4, Direction::South).instructions("LAAARRRALLLL");
+    let extended = Robot::new(8, 4, Direction::South)
+        .try_instructions("L3A3RA4L")
+        .unwrap();
+    assert_eq!(plain.position(), extended.position());
+    assert_eq!(plain.direction(), extended.direction());
+
+    let robot = Robot::new(0, 0, Direction::North)
+        .try_instructions("4(AR)U2B")
+        .unwrap();
+    assert_eq!((0, 2), robot.position());
+    assert_eq!(&Direction::South, robot.direction());
+}
+
+#[test]
+fn bad_instructions_report_their_position() {
+    assert_eq!(
+        "LAX".try_decode_instructions(),
+        Err(ParseError::InvalidCharacter {
+            position: 2,
+            found: 'X'
+        })
+    );
+    assert_eq!(
+        Robot::new(0, 0, Direction::North)
+            .try_instructions("2(AR")
+            .err()
+            .map(|e| e.position()),
+        Some(1)
+    );
+}
+
+#[test]
+fn huge_repeats_are_rejected() {
+    let robot = Robot::new(0, 0, Direction::North);
+    assert_eq!(
+        robot.try_instructions("99999999999999A"),
+        Err(ParseError::InvalidCount { position: 0 })
+    );
+    assert_eq!(
+        robot.try_instructions("9999(9999(9999(A)))"),
+        Err(ParseError::InvalidCount { position: 5 })
+    );
+}
+
+#[test]
+fn deeply_nested_groups_are_rejected() {
+    let robot = Robot::new(0, 0, Direction::North);
+    assert_eq!(
+        robot.try_instructions("(".repeat(1_000_000)),
+        Err(ParseError::TooDeep {
+            position: MAX_DEPTH
+        })
+    );
+}
@@ -0,0 +1,166 @@
+use crate::{parse_tonic, Note, ScaleResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    // Triads
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    Suspended2,
+    Suspended4,
+    // Sevenths
+    Dominant7,
+    Major7,
+    Minor7,
+    MinorMajor7,
+    HalfDiminished7,
+    Diminished7,
+    // Extensions
+    Dominant9,
+    Major9,
+    Minor9,
+    Dominant11,
+    Dominant13,
+}
+
+impl ChordQuality {
+    /// Letter names and half steps above the root for each chord tone, lowest first.
+    /// Thirds are two letters up, fifths four, sevenths six and so on.
+    /// O(1)
+    fn tones(self) -> &'static [(u8, u8)] {
+        match self {
+            ChordQuality::Major => &[(0, 0), (2, 4), (4, 7)],
+            ChordQuality::Minor => &[(0, 0), (2, 3), (4, 7)],
+            ChordQuality::Diminished => &[(0, 0), (2, 3), (4, 6)],
+            ChordQuality::Augmented => &[(0, 0), (2, 4), (4, 8)],
+            ChordQuality::Suspended2 => &[(0, 0), (1, 2), (4, 7)],
+            ChordQuality::Suspended4 => &[(0, 0), (3, 5), (4, 7)],
+            ChordQuality::Dominant7 => &[(0, 0), (2, 4), (4, 7), (6, 10)],
+            ChordQuality::Major7 => &[(0, 0), (2, 4), (4, 7), (6, 11)],
+            ChordQuality::Minor7 => &[(0, 0), (2, 3), (4, 7), (6, 10)],
+            ChordQuality::MinorMajor7 => &[(0, 0), (2, 3), (4, 7), (6, 11)],
+            ChordQuality::HalfDiminished7 => &[(0, 0), (2, 3), (4, 6), (6, 10)],
+            ChordQuality::Diminished7 => &[(0, 0), (2, 3), (4, 6), (6, 9)],
+            ChordQuality::Dominant9 => &[(0, 0), (2, 4), (4, 7), (6, 10), (8, 14)],
+            ChordQuality::Major9 => &[(0, 0), (2, 4), (4, 7), (6, 11), (8, 14)],
+            ChordQuality::Minor9 => &[(0, 0), (2, 3), (4, 7), (6, 10), (8, 14)],
+            ChordQuality::Dominant11 => &[(0, 0), (2, 4), (4, 7), (6, 10), (8, 14), (10, 17)],
+            ChordQuality::Dominant13 => {
+                &[(0, 0), (2, 4), (4, 7), (6, 10), (8, 14), (10, 17), (12, 21)]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chord {
+    notes: Vec<Note>,
+}
+
+impl Chord {
+    /// Root position chord, with each tone spelled from its own letter name
+    /// O(n) time, O(n) space
+    pub fn new(root: &str, quality: ChordQuality) -> ScaleResult<Self> {
+        let root = parse_tonic(root)?;
+        let notes = quality
+            .tones()
+            .iter()
+            .map(|&(letters, half_steps)| root.up(letters, half_steps))
+            .collect();
+
+        Ok(Chord { notes })
+    }
+
+    /// The nth inversion moves the lowest n notes up an octave.
+    /// O(n) time, O(n) space
+    pub fn inversion(&self, n: usize) -> Self {
+        let mut notes = self.notes.clone();
+        let len = notes.len();
+        notes.rotate_left(n % len);
+        Chord { notes }
+    }
+
+    /// O(n) time, O(n) space
+    pub fn enumerate(&self) -> Vec<String> {
+        self.notes.iter().map(|note| note.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn test(root: &str, quality: ChordQuality, expected: &[&str]) {
+        assert_eq!(Chord::new(root, quality).unwrap().enumerate(), expected);
+    }
+
+    #[test]
+    fn triads_work() {
+        test("C", ChordQuality::Major, &["C", "E", "G"]);
+        test("A", ChordQuality::Minor, &["A", "C", "E"]);
+        test("B", ChordQuality::Diminished, &["B", "D", "F"]);
+        test("C", ChordQuality::Augmented, &["C", "E", "G#"]);
+        test("D", ChordQuality::Suspended2, &["D", "E", "A"]);
+        test("D", ChordQuality::Suspended4, &["D", "G", "A"]);
+        test("Bb", ChordQuality::Major, &["Bb", "D", "F"]);
+        test("E", ChordQuality::Major, &["E", "G#", "B"]);
+    }
+
+    #[test]
+    fn tones_are_spelled_by_letter() {
+        test("C", ChordQuality::Minor, &["C", "Eb", "G"]);
+        test("f#", ChordQuality::Minor, &["F#", "A", "C#"]);
+        test("C#", ChordQuality::Major, &["C#", "E#", "G#"]);
+        test("Cb", ChordQuality::Major, &["Cb", "Eb", "Gb"]);
+        test("G#", ChordQuality::Augmented, &["G#", "B#", "D##"]);
+        test("C", ChordQuality::Diminished7, &["C", "Eb", "Gb", "Bbb"]);
+    }
+
+    #[test]
+    fn sevenths_work() {
+        test("C", ChordQuality::Dominant7, &["C", "E", "G", "Bb"]);
+        test("G", ChordQuality::Dominant7, &["G", "B", "D", "F"]);
+        test("F", ChordQuality::Major7, &["F", "A", "C", "E"]);
+        test("D", ChordQuality::Minor7, &["D", "F", "A", "C"]);
+        test("c", ChordQuality::MinorMajor7, &["C", "Eb", "G", "B"]);
+        test("B", ChordQuality::HalfDiminished7, &["B", "D", "F", "A"]);
+        test("b", ChordQuality::Diminished7, &["B", "D", "F", "Ab"]);
+    }
+
+    #[test]
+    fn extensions_work() {
+        test("C", ChordQuality::Dominant9, &["C", "E", "G", "Bb", "D"]);
+        test("F", ChordQuality::Major9, &["F", "A", "C", "E", "G"]);
+        test("A", ChordQuality::Minor9, &["A", "C", "E", "G", "B"]);
+        test(
+            "G",
+            ChordQuality::Dominant11,
+            &["G", "B", "D", "F", "A", "C"],
+        );
+        test(
+            "G",
+            ChordQuality::Dominant13,
+            &["G", "B", "D", "F", "A", "C", "E"],
+        );
+    }
+
+    #[test]
+    fn inversions_work() {
+        let c = Chord::new("C", ChordQuality::Major7).unwrap();
+        assert_eq!(c.inversion(0), c);
+        assert_eq!(c.inversion(1).enumerate(), vec!["E", "G", "B", "C"]);
+        assert_eq!(c.inversion(2).enumerate(), vec!["G", "B", "C", "E"]);
+        assert_eq!(c.inversion(3).enumerate(), vec!["B", "C", "E", "G"]);
+        assert_eq!(c.inversion(4), c);
+    }
+
+    #[test]
+    fn bad_roots_are_errors() {
+        assert_eq!(
+            Chord::new("H", ChordQuality::Major),
+            Err(Error::InvalidTonic("H".to_string()))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Error, ScaleResult};
+
+use std::str::FromStr;
+
+/// Half steps in the major or perfect interval of each size, unison through seventh
+const MAJOR_OR_PERFECT: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// A distance between notes, counted both in letter names and half steps so that
+/// transposing keeps the spelling right: C up a major third is E, up a diminished
+/// fourth is Fb.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    letters: u8,
+    half_steps: u8,
+}
+
+impl Interval {
+    pub const UNISON: Interval = Interval::new(0, 0);
+    pub const MINOR_SECOND: Interval = Interval::new(1, 1);
+    pub const MAJOR_SECOND: Interval = Interval::new(1, 2);
+    pub const MINOR_THIRD: Interval = Interval::new(2, 3);
+    pub const MAJOR_THIRD: Interval = Interval::new(2, 4);
+    pub const PERFECT_FOURTH: Interval = Interval::new(3, 5);
+    pub const AUGMENTED_FOURTH: Interval = Interval::new(3, 6);
+    pub const DIMINISHED_FIFTH: Interval = Interval::new(4, 6);
+    pub const PERFECT_FIFTH: Interval = Interval::new(4, 7);
+    pub const MINOR_SIXTH: Interval = Interval::new(5, 8);
+    pub const MAJOR_SIXTH: Interval = Interval::new(5, 9);
+    pub const MINOR_SEVENTH: Interval = Interval::new(6, 10);
+    pub const MAJOR_SEVENTH: Interval = Interval::new(6, 11);
+    pub const OCTAVE: Interval = Interval::new(7, 12);
+
+    /// `letters` is one less than the interval's number, so a third spans two letters
+    pub const fn new(letters: u8, half_steps: u8) -> Self {
+        Interval {
+            letters,
+            half_steps,
+        }
+    }
+
+    pub fn letters(self) -> u8 {
+        self.letters
+    }
+
+    pub fn half_steps(self) -> u8 {
+        self.half_steps
+    }
+}
+
+/// Parses a quality (`P`, `M`, `m`, `A` or `d`) followed by a number, like `M3`,
+/// `P5` or `m9`. Intervals wider than 255 half steps are rejected.
+impl FromStr for Interval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> ScaleResult<Self> {
+        let invalid = || Error::InvalidInterval(s.to_string());
+        let mut chars = s.chars();
+        let quality = chars.next().ok_or_else(invalid)?;
+        let number: u8 = chars.as_str().parse().map_err(|_| invalid())?;
+        if number == 0 {
+            return Err(invalid());
+        }
+
+        let letters = number - 1;
+        let base = (letters / 7)
+            .checked_mul(12)
+            .and_then(|octaves| octaves.checked_add(MAJOR_OR_PERFECT[(letters % 7) as usize]))
+            .ok_or_else(invalid)?;
+        let perfect = matches!(letters % 7, 0 | 3 | 4);
+        let half_steps = match (quality, perfect) {
+            ('P', true) | ('M', false) => Some(base),
+            ('A', _) => base.checked_add(1),
+            ('m', false) | ('d', true) => base.checked_sub(1),
+            ('d', false) => base.checked_sub(2),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+
+        Ok(Interval::new(letters, half_steps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        assert_eq!("P1".parse(), Ok(Interval::UNISON));
+        assert_eq!("m2".parse(), Ok(Interval::MINOR_SECOND));
+        assert_eq!("M3".parse(), Ok(Interval::MAJOR_THIRD));
+        assert_eq!("A4".parse(), Ok(Interval::AUGMENTED_FOURTH));
+        assert_eq!("d5".parse(), Ok(Interval::DIMINISHED_FIFTH));
+        assert_eq!("P5".parse(), Ok(Interval::PERFECT_FIFTH));
+        assert_eq!("m7".parse(), Ok(Interval::MINOR_SEVENTH));
+        assert_eq!("d7".parse(), Ok(Interval::new(6, 9)));
+        assert_eq!("P8".parse(), Ok(Interval::OCTAVE));
+        assert_eq!("M9".parse(), Ok(Interval::new(8, 14)));
+    }
+
+    #[test]
+    fn parse_rejects_bad_intervals() {
+        for bad in ["", "P", "M5", "P3", "d1", "X3", "P0", "M-2"].iter() {
+            assert_eq!(
+                bad.parse::<Interval>(),
+                Err(Error::InvalidInterval(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_intervals_too_wide_to_count() {
+        assert_eq!("P148".parse(), Ok(Interval::new(147, 252)));
+        assert_eq!("A148".parse(), Ok(Interval::new(147, 253)));
+        for bad in ["P150", "A255", "M156", "P256"].iter() {
+            assert_eq!(
+                bad.parse::<Interval>(),
+                Err(Error::InvalidInterval(bad.to_string()))
+            );
+        }
+    }
+}
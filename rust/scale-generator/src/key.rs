@@ -0,0 +1,261 @@
+use crate::{string_to_note, Error, Mode, Note, PitchClass, Scale, ScaleResult};
+
+/// Krumhansl-Kessler probe tone ratings, by half steps above the tonic
+const MAJOR_PROFILE: [f64; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+const MINOR_PROFILE: [f64; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+const MODES: [Mode; 7] = [
+    Mode::Ionian,
+    Mode::Dorian,
+    Mode::Phrygian,
+    Mode::Lydian,
+    Mode::Mixolydian,
+    Mode::Aeolian,
+    Mode::Locrian,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyProfile {
+    /// Correlates the notes with Krumhansl's major and minor key profiles.
+    /// Confidence is the correlation, from -1 to 1.
+    Krumhansl,
+    /// Counts the notes that fall in each mode on each tonic. Confidence is the
+    /// fraction that do, from 0 to 1. Modes with the same notes, like C Ionian
+    /// and A Aeolian, are told apart by how often their tonic is played.
+    Membership,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCandidate {
+    pub tonic: Note,
+    pub mode: Mode,
+    pub scale: Scale,
+    pub confidence: f64,
+}
+
+/// Ranks keys for a list of note names, most likely first
+/// O(n) time, O(1) space besides the input
+pub fn detect_key(notes: &[&str], profile: KeyProfile) -> ScaleResult<Vec<KeyCandidate>> {
+    let pitch_classes = notes
+        .iter()
+        .map(|n| string_to_note(n))
+        .collect::<ScaleResult<Vec<_>>>()?;
+    rank_keys(&pitch_classes, profile)
+}
+
+/// Ranks keys for a list of MIDI note numbers, most likely first
+/// O(n) time, O(1) space besides the input
+pub fn detect_key_midi(numbers: &[u8], profile: KeyProfile) -> ScaleResult<Vec<KeyCandidate>> {
+    let pitch_classes: Vec<PitchClass> = numbers
+        .iter()
+        .map(|&n| (n as i16 - 69).rem_euclid(12) as PitchClass)
+        .collect();
+    rank_keys(&pitch_classes, profile)
+}
+
+/// O(n)
+fn rank_keys(pitch_classes: &[PitchClass], profile: KeyProfile) -> ScaleResult<Vec<KeyCandidate>> {
+    if pitch_classes.is_empty() {
+        return Err(Error::Undeterminiate);
+    }
+
+    let mut histogram = [0.0; 12];
+    for &pc in pitch_classes {
+        histogram[pc as usize] += 1.0;
+    }
+
+    // (confidence, tie breaker, tonic, mode)
+    let mut ranked: Vec<(f64, f64, PitchClass, Mode)> = Vec::new();
+    for tonic in 0..12 {
+        match profile {
+            KeyProfile::Krumhansl => {
+                for &(mode, weights) in [
+                    (Mode::Ionian, &MAJOR_PROFILE),
+                    (Mode::Aeolian, &MINOR_PROFILE),
+                ]
+                .iter()
+                {
+                    let rotated: Vec<f64> = (0..12)
+                        .map(|pc| weights[(pc + 12 - tonic as usize) % 12])
+                        .collect();
+                    ranked.push((correlation(&histogram, &rotated), 0.0, tonic, mode));
+                }
+            }
+            KeyProfile::Membership => {
+                for &mode in MODES.iter() {
+                    let members = mode_pitch_classes(tonic, mode);
+                    let inside: f64 = members.iter().map(|&pc| histogram[pc as usize]).sum();
+                    let confidence = inside / pitch_classes.len() as f64;
+                    ranked.push((confidence, histogram[tonic as usize], tonic, mode));
+                }
+            }
+        }
+    }
+    ranked.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap()
+            .then(b.1.partial_cmp(&a.1).unwrap())
+    });
+
+    Ok(ranked
+        .into_iter()
+        .map(|(confidence, _, tonic, mode)| {
+            let (tonic, scale) = spell_key(tonic, mode);
+            KeyCandidate {
+                tonic,
+                mode,
+                scale,
+                confidence,
+            }
+        })
+        .collect())
+}
+
+/// Pearson correlation, or 0 when either side is flat
+/// O(1), both sides have 12 entries
+fn correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+    let (mx, my) = (mean(xs), mean(ys));
+    let (mut cov, mut vx, mut vy) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        cov += (x - mx) * (y - my);
+        vx += (x - mx) * (x - mx);
+        vy += (y - my) * (y - my);
+    }
+    if vx == 0.0 || vy == 0.0 {
+        0.0
+    } else {
+        cov / (vx * vy).sqrt()
+    }
+}
+
+/// O(1)
+fn mode_pitch_classes(tonic: PitchClass, mode: Mode) -> Vec<PitchClass> {
+    let root = Note::from_pitch_class(tonic, false);
+    Scale::from_intervals(root, false, &mode.intervals()[..6])
+        .expect("mode intervals are valid")
+        .notes
+        .iter()
+        .map(|n| n.pitch_class())
+        .collect()
+}
+
+/// Spells the tonic with a sharp or a flat, whichever gives the key fewer accidentals
+/// O(1)
+fn spell_key(tonic: PitchClass, mode: Mode) -> (Note, Scale) {
+    let accidentals = |scale: &Scale| -> i32 {
+        scale
+            .notes
+            .iter()
+            .map(|n| n.accidental().abs() as i32)
+            .sum()
+    };
+
+    [false, true]
+        .iter()
+        .map(|&flats| {
+            let root = Note::from_pitch_class(tonic, flats);
+            let scale = Scale::from_intervals(root, flats, mode.intervals())
+                .expect("mode intervals are valid");
+            (root, scale)
+        })
+        .min_by_key(|(_, scale)| accidentals(scale))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(notes: &str, profile: KeyProfile) -> KeyCandidate {
+        let notes: Vec<&str> = notes.split_whitespace().collect();
+        detect_key(&notes, profile).unwrap().remove(0)
+    }
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn krumhansl_finds_c_major() {
+        let key = top("C D E F G A B C E G C G", KeyProfile::Krumhansl);
+        assert_eq!(key.tonic, note("C"));
+        assert_eq!(key.mode, Mode::Ionian);
+        assert!(key.confidence > 0.8);
+    }
+
+    #[test]
+    fn krumhansl_finds_a_minor() {
+        let key = top("A C E A B C D E G# A E A", KeyProfile::Krumhansl);
+        assert_eq!(key.tonic, note("A"));
+        assert_eq!(key.mode, Mode::Aeolian);
+    }
+
+    #[test]
+    fn krumhansl_ranks_every_major_and_minor_key() {
+        let ranked = detect_key(&["C", "E", "G"], KeyProfile::Krumhansl).unwrap();
+        assert_eq!(ranked.len(), 24);
+        assert!(ranked
+            .windows(2)
+            .all(|w| w[0].confidence >= w[1].confidence));
+    }
+
+    #[test]
+    fn membership_breaks_ties_by_tonic() {
+        let ranked = detect_key(
+            &["D", "E", "F", "G", "A", "B", "C", "D", "A", "D"],
+            KeyProfile::Membership,
+        )
+        .unwrap();
+        assert_eq!(ranked.len(), 84);
+        assert_eq!(ranked[0].tonic, note("D"));
+        assert_eq!(ranked[0].mode, Mode::Dorian);
+        assert_eq!(ranked[0].confidence, 1.0);
+        assert!(ranked[1..7].iter().all(|k| k.confidence == 1.0));
+        assert!(ranked[7].confidence < 1.0);
+    }
+
+    #[test]
+    fn midi_input_works() {
+        let c_major: Vec<u8> = vec![60, 62, 64, 65, 67, 69, 71, 72, 64, 67, 60];
+        let key = detect_key_midi(&c_major, KeyProfile::Krumhansl)
+            .unwrap()
+            .remove(0);
+        assert_eq!(key.tonic, note("C"));
+        assert_eq!(key.mode, Mode::Ionian);
+    }
+
+    #[test]
+    fn keys_are_spelled_with_fewest_accidentals() {
+        let key = top("Db Eb F Gb Ab Bb C Db Ab F Db", KeyProfile::Krumhansl);
+        assert_eq!(key.tonic, note("Db"));
+        assert_eq!(
+            key.scale.enumerate(),
+            vec!["Db", "Eb", "F", "Gb", "Ab", "Bb", "C", "Db"]
+        );
+
+        let key = top("C# D# E F# G# A B# C# G# E C#", KeyProfile::Krumhansl);
+        assert_eq!(key.tonic, note("C#"));
+        assert_eq!(key.mode, Mode::Aeolian);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            detect_key(&[], KeyProfile::Krumhansl),
+            Err(Error::Undeterminiate)
+        );
+        assert_eq!(
+            detect_key_midi(&[], KeyProfile::Membership),
+            Err(Error::Undeterminiate)
+        );
+        assert_eq!(
+            detect_key(&["C", "H"], KeyProfile::Krumhansl),
+            Err(Error::InvalidNote("H".to_string()))
+        );
+    }
+}
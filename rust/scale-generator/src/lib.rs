@@ -1,73 +1,92 @@
 #[derive(Debug, PartialEq)]
 pub enum Error {
     InvalidNote(String),
+    InvalidInterval(String),
     InvalidTonic(String),
     InvalidStep(char),
+    /// A pitch whose octave doesn't fit in an `i8`
+    InvalidOctave(i16),
     Undeterminiate,
 }
 
+mod chord;
+mod interval;
+mod key;
+mod note;
+mod pitch;
+pub use chord::{Chord, ChordQuality};
+pub use interval::Interval;
+pub use key::{detect_key, detect_key_midi, KeyCandidate, KeyProfile};
+pub use note::{Note, PitchClass};
+pub use pitch::{Pitch, Temperament, Tuning};
+
 type ScaleResult<T> = Result<T, Error>;
-type Notes = [&'static str; 12];
 
-/// Note is u8 because the note should never exceed 11
-type Note = u8;
-pub struct Scale {
-    flats: bool,
-    notes: Vec<Note>,
+/// The seven church modes, as rotations of the major scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Aeolian,
+    Locrian,
 }
 
-/// O(1) time, O(1) space
-fn note_string(note: Note, flats: bool) -> String {
-    const SHARPS: Notes = [
-        "A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#",
-    ];
-
-    const FLATS: Notes = [
-        "A", "Bb", "B", "C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab",
-    ];
+impl Mode {
+    /// O(1)
+    fn intervals(self) -> &'static str {
+        match self {
+            Mode::Ionian => "MMmMMMm",
+            Mode::Dorian => "MmMMMmM",
+            Mode::Phrygian => "mMMMmMM",
+            Mode::Lydian => "MMMmMMm",
+            Mode::Mixolydian => "MMmMMmM",
+            Mode::Aeolian => "MmMMmMM",
+            Mode::Locrian => "mMMmMMM",
+        }
+    }
 
-    if flats {
-        FLATS[note as usize].to_string()
-    } else {
-        SHARPS[note as usize].to_string()
+    /// Modes with a minor third are spelled like a minor key
+    fn is_minor(self) -> bool {
+        matches!(
+            self,
+            Mode::Dorian | Mode::Phrygian | Mode::Aeolian | Mode::Locrian
+        )
     }
 }
 
-/// O(1)
-fn string_to_note(note_str: &str) -> ScaleResult<Note> {
-    let invalid_note_fn = || Error::InvalidNote(note_str.to_string());
-    let mut byte_iter = note_str.as_bytes().iter();
-
-    let mut sum = match byte_iter
-        .next()
-        .ok_or_else(invalid_note_fn)?
-        .to_ascii_uppercase()
-    {
-        b @ (b'A' | b'B') => (b - b'A') as i8 * 2,
-        b @ (b'C'..=b'E') => (b - b'A') as i8 * 2 - 1,
-        b @ (b'F' | b'G') => (b - b'A') as i8 * 2 - 2,
-        _ => return Err(invalid_note_fn()),
-    };
-
-    if let Some(b) = byte_iter.next() {
-        match b {
-            b'#' => sum += 1,
-            b'b' => sum -= 1,
-            _ => return Err(invalid_note_fn()),
-        }
-    }
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scale {
+    notes: Vec<Note>,
+}
 
-    // Should not have more than 2 characters. A note and its accent
-    if byte_iter.next().is_some() {
-        return Err(invalid_note_fn());
-    }
+/// Parses a note name to its pitch class
+/// O(1)
+pub fn string_to_note(note_str: &str) -> ScaleResult<PitchClass> {
+    note_str.parse::<Note>().map(Note::pitch_class)
+}
 
-    // A flat should be half step 11
-    if sum < 0 {
-        sum += 12;
-    }
+/// A tonic that doesn't parse is reported as an invalid tonic rather than note
+/// O(1)
+fn parse_tonic(tonic: &str) -> ScaleResult<Note> {
+    tonic
+        .parse()
+        .map_err(|_| Error::InvalidTonic(tonic.to_string()))
+}
 
-    Ok(sum as Note)
+/// O(n)
+fn half_steps(intervals: &str) -> ScaleResult<Vec<u8>> {
+    intervals
+        .chars()
+        .map(|step| match step {
+            'm' => Ok(1),
+            'M' => Ok(2),
+            'A' => Ok(3),
+            c => Err(Error::InvalidStep(c)),
+        })
+        .collect()
 }
 
 impl Scale {
@@ -75,66 +94,157 @@ impl Scale {
     /// O(n) Time, O(n) Space
     pub fn new(tonic: &str, intervals: &str) -> ScaleResult<Scale> {
         let flats = use_flats(tonic)?;
-        let root = string_to_note(tonic)?;
+        let root = parse_tonic(tonic)?;
+        Self::from_intervals(root, flats, intervals)
+    }
 
-        let notes = intervals
-            .as_bytes()
+    /// Seven note scales take each letter name once, so F# major has an E# and
+    /// C harmonic minor a B natural. Other scales, and seven note scales that would
+    /// need more than a double sharp or flat, are spelled with the key's sharps or flats.
+    /// O(n) Time, O(n) Space
+    fn from_intervals(root: Note, flats: bool, intervals: &str) -> ScaleResult<Scale> {
+        let offsets: Vec<u8> = half_steps(intervals)?
             .iter()
-            .try_fold((root, vec![root]), |(mut note, mut acc), b| {
-                match b {
-                    b'm' => note += 1,
-                    b'M' => note += 2,
-                    b'A' => note += 3,
-                    b => return Err(Error::InvalidStep(*b as char)),
-                }
-                note %= 12;
-                acc.push(note);
-                Ok((note, acc))
-            })?
-            .1;
+            .scan(0, |total, step| {
+                *total = (*total + step) % 12;
+                Some(*total)
+            })
+            .collect();
+
+        let by_letter: Vec<Note> = std::iter::once(root)
+            .chain(
+                offsets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &offset)| root.up(i as u8 + 1, offset)),
+            )
+            .collect();
+        if offsets.len() == 7 && by_letter.iter().all(|n| n.accidental().abs() <= 2) {
+            return Ok(Self { notes: by_letter });
+        }
 
-        Ok(Self { flats, notes })
+        let notes = std::iter::once(root)
+            .chain(offsets.iter().map(|&offset| {
+                if offset == 0 {
+                    root
+                } else {
+                    Note::from_pitch_class(root.pitch_class() + offset, flats)
+                }
+            }))
+            .collect();
+        Ok(Self { notes })
     }
 
     /// O(1) time, O(1) space, since chromatic scales are constant length
     pub fn chromatic(tonic: &str) -> ScaleResult<Self> {
-        let flats = use_flats(tonic)?;
-        let root = string_to_note(tonic)?;
-        let notes = (0..12).cycle().skip(root as usize).take(13).collect();
+        Self::new(tonic, "mmmmmmmmmmmm")
+    }
+
+    /// A named scale is spelled like the major or minor key on its tonic,
+    /// whatever case the tonic is given in.
+    /// O(n) Time, O(n) Space
+    fn named(tonic: &str, intervals: &str, minor: bool) -> ScaleResult<Self> {
+        let flats = use_flats_for(tonic, minor)?;
+        let root = parse_tonic(tonic)?;
+        Self::from_intervals(root, flats, intervals)
+    }
+
+    pub fn mode(tonic: &str, mode: Mode) -> ScaleResult<Self> {
+        Self::named(tonic, mode.intervals(), mode.is_minor())
+    }
 
-        Ok(Scale { flats, notes })
+    pub fn major(tonic: &str) -> ScaleResult<Self> {
+        Self::mode(tonic, Mode::Ionian)
     }
 
+    pub fn natural_minor(tonic: &str) -> ScaleResult<Self> {
+        Self::mode(tonic, Mode::Aeolian)
+    }
+
+    pub fn harmonic_minor(tonic: &str) -> ScaleResult<Self> {
+        Self::named(tonic, "MmMMmAm", true)
+    }
+
+    /// The ascending form, with raised sixth and seventh
+    pub fn melodic_minor(tonic: &str) -> ScaleResult<Self> {
+        Self::named(tonic, "MmMMMMm", true)
+    }
+
+    pub fn major_pentatonic(tonic: &str) -> ScaleResult<Self> {
+        Self::named(tonic, "MMAMA", false)
+    }
+
+    pub fn minor_pentatonic(tonic: &str) -> ScaleResult<Self> {
+        Self::named(tonic, "AMMAM", true)
+    }
+
+    /// The minor pentatonic with the flat fifth added
+    pub fn blues(tonic: &str) -> ScaleResult<Self> {
+        Self::named(tonic, "AMmmAM", true)
+    }
+
+    /// The same scale on a tonic `interval` higher, keeping its spelling
     /// O(n) time, O(n) space
-    pub fn enumerate(&self) -> Vec<String> {
+    pub fn transpose(&self, interval: Interval) -> Self {
+        let notes = self
+            .notes
+            .iter()
+            .map(|n| n.up(interval.letters(), interval.half_steps()))
+            .collect();
+        Scale { notes }
+    }
+
+    /// The scale ascending from its tonic in the given octave, or an error if it climbs
+    /// past the last octave an `i8` can number
+    /// O(n) time, O(n) space
+    pub fn pitches(&self, octave: i8) -> ScaleResult<Vec<Pitch>> {
+        let mut number = Pitch::new(self.notes[0], octave).number();
+        let mut previous = self.notes[0].pitch_class();
         self.notes
             .iter()
-            .map(|note| note_string(*note, self.flats))
+            .map(|&note| {
+                number += (note.pitch_class() as i16 - previous as i16).rem_euclid(12);
+                previous = note.pitch_class();
+                Pitch::with_number(note, number)
+            })
             .collect()
     }
+
+    /// O(n) time, O(n) space
+    pub fn enumerate(&self) -> Vec<String> {
+        self.notes.iter().map(|note| note.to_string()).collect()
+    }
 }
 
+/// A key uses flats when its signature has any. Upper case indicates Major,
+/// lower case indicates minor.
 /// O(1)
 fn use_flats(tonic: &str) -> ScaleResult<bool> {
-    // Upper case indicates Major, lower case indicates minor
-    let not_flats = [
-        // Sharps
-        "G", "D", "A", "E", "B", "F#", "e", "b", "f#", "c#", "g#", "d#",
-        // These scales do not use sharps or flats so it's irrelavant which is used.
-        "C", "a",
-    ];
-
-    let flat_tonics = [
-        "F", "Bb", "Eb", "Ab", "Db", "Gb", "d", "g", "c", "f", "bb", "eb",
-    ];
+    let root = parse_tonic(tonic)?;
+    let minor = tonic.starts_with(|c: char| c.is_ascii_lowercase());
+    let intervals = if minor {
+        Mode::Aeolian.intervals()
+    } else {
+        Mode::Ionian.intervals()
+    };
 
-    // Since these arrays are constant size. Searching them is O(1)
-    let res = flat_tonics.contains(&tonic);
-    if !res && !not_flats.contains(&tonic) {
-        return Err(Error::InvalidTonic(tonic.to_string()));
-    }
+    let key = Scale::from_intervals(root, false, intervals)?;
+    Ok(key.notes.iter().any(|n| n.accidental() < 0))
+}
 
-    Ok(res)
+/// use_flats for a tonic recased as a major (upper case) or minor (lower case) key
+/// O(1)
+fn use_flats_for(tonic: &str, minor: bool) -> ScaleResult<bool> {
+    let mut chars = tonic.chars();
+    let letter = chars
+        .next()
+        .ok_or_else(|| Error::InvalidTonic(tonic.to_string()))?;
+    let letter = if minor {
+        letter.to_ascii_lowercase()
+    } else {
+        letter.to_ascii_uppercase()
+    };
+    use_flats(&format!("{}{}", letter, chars.as_str()))
 }
 
 #[cfg(test)]
@@ -199,6 +309,261 @@ mod tests {
     #[test]
     fn new_test() {
         let s = Scale::new("B", "mMA").unwrap();
-        assert_eq!(s.notes, vec![2, 3, 5, 8]);
+        let pitch_classes: Vec<PitchClass> = s.notes.iter().map(|n| n.pitch_class()).collect();
+        assert_eq!(pitch_classes, vec![2, 3, 5, 8]);
+        assert_eq!(s.enumerate(), vec!["B", "C", "D", "F"]);
+    }
+
+    #[test]
+    fn use_flats_for_recases_the_tonic() {
+        assert_eq!(use_flats_for("d", false), Ok(false));
+        assert_eq!(use_flats_for("D", true), Ok(true));
+        assert_eq!(use_flats_for("Bb", true), Ok(true));
+        assert_eq!(use_flats_for("f#", false), Ok(false));
+        assert!(use_flats_for("", false).is_err());
+    }
+
+    #[test]
+    fn modes_work() {
+        let test = |tonic: &str, mode: Mode, expected: &[&str]| {
+            assert_eq!(Scale::mode(tonic, mode).unwrap().enumerate(), expected)
+        };
+
+        test("C", Mode::Ionian, &["C", "D", "E", "F", "G", "A", "B", "C"]);
+        test("D", Mode::Dorian, &["D", "E", "F", "G", "A", "B", "C", "D"]);
+        test(
+            "E",
+            Mode::Phrygian,
+            &["E", "F", "G", "A", "B", "C", "D", "E"],
+        );
+        test("F", Mode::Lydian, &["F", "G", "A", "B", "C", "D", "E", "F"]);
+        test(
+            "G",
+            Mode::Mixolydian,
+            &["G", "A", "B", "C", "D", "E", "F", "G"],
+        );
+        test(
+            "A",
+            Mode::Aeolian,
+            &["A", "B", "C", "D", "E", "F", "G", "A"],
+        );
+        test(
+            "B",
+            Mode::Locrian,
+            &["B", "C", "D", "E", "F", "G", "A", "B"],
+        );
+        test(
+            "g",
+            Mode::Dorian,
+            &["G", "A", "Bb", "C", "D", "E", "F", "G"],
+        );
+        test(
+            "eb",
+            Mode::Mixolydian,
+            &["Eb", "F", "G", "Ab", "Bb", "C", "Db", "Eb"],
+        );
+    }
+
+    #[test]
+    fn named_scales_work() {
+        let test = |scale: ScaleResult<Scale>, expected: &[&str]| {
+            assert_eq!(scale.unwrap().enumerate(), expected)
+        };
+
+        test(
+            Scale::major("G"),
+            &["G", "A", "B", "C", "D", "E", "F#", "G"],
+        );
+        test(
+            Scale::natural_minor("C"),
+            &["C", "D", "Eb", "F", "G", "Ab", "Bb", "C"],
+        );
+        test(
+            Scale::harmonic_minor("a"),
+            &["A", "B", "C", "D", "E", "F", "G#", "A"],
+        );
+        test(
+            Scale::melodic_minor("a"),
+            &["A", "B", "C", "D", "E", "F#", "G#", "A"],
+        );
+        test(
+            Scale::major_pentatonic("C"),
+            &["C", "D", "E", "G", "A", "C"],
+        );
+        test(
+            Scale::minor_pentatonic("A"),
+            &["A", "C", "D", "E", "G", "A"],
+        );
+        test(Scale::blues("e"), &["E", "G", "A", "A#", "B", "D", "E"]);
+        test(Scale::blues("C"), &["C", "Eb", "F", "Gb", "G", "Bb", "C"]);
+    }
+
+    #[test]
+    fn named_scales_reject_bad_tonics() {
+        assert_eq!(
+            Scale::major("H").err(),
+            Some(Error::InvalidTonic("H".to_string()))
+        );
+        assert!(Scale::major("").is_err());
+    }
+
+    /// The 15 major keys, each with its relative minor
+    const KEYS: [(&str, &str, [&str; 7]); 15] = [
+        ("C", "a", ["C", "D", "E", "F", "G", "A", "B"]),
+        ("G", "e", ["G", "A", "B", "C", "D", "E", "F#"]),
+        ("D", "b", ["D", "E", "F#", "G", "A", "B", "C#"]),
+        ("A", "f#", ["A", "B", "C#", "D", "E", "F#", "G#"]),
+        ("E", "c#", ["E", "F#", "G#", "A", "B", "C#", "D#"]),
+        ("B", "g#", ["B", "C#", "D#", "E", "F#", "G#", "A#"]),
+        ("F#", "d#", ["F#", "G#", "A#", "B", "C#", "D#", "E#"]),
+        ("C#", "a#", ["C#", "D#", "E#", "F#", "G#", "A#", "B#"]),
+        ("F", "d", ["F", "G", "A", "Bb", "C", "D", "E"]),
+        ("Bb", "g", ["Bb", "C", "D", "Eb", "F", "G", "A"]),
+        ("Eb", "c", ["Eb", "F", "G", "Ab", "Bb", "C", "D"]),
+        ("Ab", "f", ["Ab", "Bb", "C", "Db", "Eb", "F", "G"]),
+        ("Db", "bb", ["Db", "Eb", "F", "Gb", "Ab", "Bb", "C"]),
+        ("Gb", "eb", ["Gb", "Ab", "Bb", "Cb", "Db", "Eb", "F"]),
+        ("Cb", "ab", ["Cb", "Db", "Eb", "Fb", "Gb", "Ab", "Bb"]),
+    ];
+
+    #[test]
+    fn all_major_key_signatures() {
+        for (major, _, notes) in KEYS.iter() {
+            let mut expected = notes.to_vec();
+            expected.push(notes[0]);
+            assert_eq!(Scale::new(major, "MMmMMMm").unwrap().enumerate(), expected);
+        }
+    }
+
+    #[test]
+    fn all_minor_key_signatures() {
+        for (_, minor, notes) in KEYS.iter() {
+            let mut expected = notes.to_vec();
+            expected.rotate_left(5);
+            expected.push(expected[0]);
+            assert_eq!(Scale::new(minor, "MmMMmMM").unwrap().enumerate(), expected);
+        }
+    }
+
+    #[test]
+    fn key_signatures_choose_flats() {
+        for (major, minor, notes) in KEYS.iter() {
+            let flats = notes.iter().any(|n| n.ends_with('b'));
+            assert_eq!(use_flats(major), Ok(flats), "{}", major);
+            assert_eq!(use_flats(minor), Ok(flats), "{}", minor);
+        }
+    }
+
+    #[test]
+    fn seven_note_scales_use_each_letter_once() {
+        let test = |tonic: &str, intervals: &str, expected: &[&str]| {
+            assert_eq!(Scale::new(tonic, intervals).unwrap().enumerate(), expected)
+        };
+
+        test(
+            "c#",
+            "MmMMmAm",
+            &["C#", "D#", "E", "F#", "G#", "A", "B#", "C#"],
+        );
+        test(
+            "G#",
+            "MMmMMMm",
+            &["G#", "A#", "B#", "C#", "D#", "E#", "F##", "G#"],
+        );
+        test(
+            "Fb",
+            "MMmMMMm",
+            &["Fb", "Gb", "Ab", "Bbb", "Cb", "Db", "Eb", "Fb"],
+        );
+        test(
+            "C",
+            "mMMmMMM",
+            &["C", "Db", "Eb", "F", "Gb", "Ab", "Bb", "C"],
+        );
+    }
+
+    #[test]
+    fn other_scales_keep_the_tonic_spelling() {
+        let s = Scale::chromatic("Cb").unwrap().enumerate();
+        assert_eq!(s.first().map(String::as_str), Some("Cb"));
+        assert_eq!(s.last().map(String::as_str), Some("Cb"));
+        assert_eq!(s[1], "C");
+    }
+
+    #[test]
+    fn string_to_note_accepts_double_accidentals() {
+        assert_eq!(string_to_note("F##"), Ok(10));
+        assert_eq!(string_to_note("Fx"), Ok(10));
+        assert_eq!(string_to_note("Bbb"), Ok(0));
+        assert_eq!(string_to_note("Cb"), Ok(2));
+        assert_eq!(
+            string_to_note("C#b"),
+            Err(Error::InvalidNote("C#b".to_string()))
+        );
+    }
+
+    #[test]
+    fn transpose_works() {
+        let c = Scale::major("C").unwrap();
+        assert_eq!(
+            c.transpose(Interval::MAJOR_THIRD).enumerate(),
+            Scale::major("E").unwrap().enumerate()
+        );
+        assert_eq!(
+            Scale::major("F#")
+                .unwrap()
+                .transpose(Interval::MINOR_SECOND)
+                .enumerate(),
+            Scale::major("G").unwrap().enumerate()
+        );
+        assert_eq!(
+            c.transpose(Interval::new(0, 1)).enumerate(),
+            vec!["C#", "D#", "E#", "F#", "G#", "A#", "B#", "C#"]
+        );
+        assert_eq!(
+            Scale::harmonic_minor("d")
+                .unwrap()
+                .transpose("P5".parse().unwrap())
+                .enumerate(),
+            Scale::harmonic_minor("a").unwrap().enumerate()
+        );
+    }
+
+    #[test]
+    fn pitches_ascend_through_octaves() {
+        let pitches: Vec<String> = Scale::major("A")
+            .unwrap()
+            .pitches(3)
+            .unwrap()
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(
+            pitches,
+            vec!["A3", "B3", "C#4", "D4", "E4", "F#4", "G#4", "A4"]
+        );
+
+        let midi: Vec<Option<u8>> = Scale::major("C#")
+            .unwrap()
+            .pitches(4)
+            .unwrap()
+            .iter()
+            .map(|p| p.midi())
+            .collect();
+        let expected: Vec<Option<u8>> = [61, 63, 65, 66, 68, 70, 72, 73]
+            .iter()
+            .map(|&n| Some(n))
+            .collect();
+        assert_eq!(midi, expected);
+    }
+
+    #[test]
+    fn pitches_stop_at_the_last_octave() {
+        let pitches = Scale::major("C").unwrap().pitches(126).unwrap();
+        assert_eq!(pitches.last().unwrap().to_string(), "C127");
+        assert_eq!(
+            Scale::major("C").unwrap().pitches(127),
+            Err(Error::InvalidOctave(128))
+        );
     }
 }
@@ -0,0 +1,179 @@
+use crate::{Error, ScaleResult};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Half steps above A, 0 through 11
+pub type PitchClass = u8;
+
+/// Letter names are indexed from A = 0 to G = 6
+type Letter = u8;
+
+const LETTERS: [char; 7] = ['A', 'B', 'C', 'D', 'E', 'F', 'G'];
+
+/// The pitch class of each natural note
+const NATURALS: [PitchClass; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+/// Half steps from C up to each natural note, since octaves are numbered from C
+const ABOVE_C: [i8; 7] = [9, 11, 0, 2, 4, 5, 7];
+
+/// A spelled note: a letter name raised or lowered by a number of half steps.
+/// E# and F are different notes with the same pitch class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Note {
+    letter: Letter,
+    accidental: i8,
+}
+
+impl Note {
+    /// O(1)
+    pub fn pitch_class(self) -> PitchClass {
+        (NATURALS[self.letter as usize] as i8 + self.accidental).rem_euclid(12) as PitchClass
+    }
+
+    /// Sharps (positive) or flats (negative) on the letter
+    pub fn accidental(self) -> i8 {
+        self.accidental
+    }
+
+    /// Half steps above the C with the same octave number, so B# is 12 and Cb is -1
+    pub(crate) fn above_c(self) -> i8 {
+        ABOVE_C[self.letter as usize] + self.accidental
+    }
+
+    /// The note `letters` letter names above this one, spelled so that it sounds
+    /// `half_steps` higher. O(1)
+    pub(crate) fn up(self, letters: u8, half_steps: u8) -> Note {
+        let letter = (self.letter + letters % 7) % 7;
+        let target = (self.pitch_class() as i8 + (half_steps % 12) as i8) % 12;
+        let mut accidental = target - NATURALS[letter as usize] as i8;
+        if accidental > 6 {
+            accidental -= 12;
+        } else if accidental < -6 {
+            accidental += 12;
+        }
+        Note { letter, accidental }
+    }
+
+    /// Spells a pitch class with at most one sharp or flat. O(1)
+    pub(crate) fn from_pitch_class(pitch_class: PitchClass, flats: bool) -> Note {
+        let pitch_class = pitch_class % 12;
+        match NATURALS.iter().position(|&n| n == pitch_class) {
+            Some(letter) => Note {
+                letter: letter as Letter,
+                accidental: 0,
+            },
+            None if flats => Note::from_pitch_class(pitch_class + 1, flats).flat(),
+            None => Note::from_pitch_class(pitch_class + 11, flats).sharp(),
+        }
+    }
+
+    fn sharp(self) -> Note {
+        Note {
+            accidental: self.accidental + 1,
+            ..self
+        }
+    }
+
+    fn flat(self) -> Note {
+        Note {
+            accidental: self.accidental - 1,
+            ..self
+        }
+    }
+}
+
+/// Parses a letter, in either case, followed by one of `#`, `##`, `x`, `b` or `bb`.
+impl FromStr for Note {
+    type Err = Error;
+
+    fn from_str(note_str: &str) -> ScaleResult<Note> {
+        let invalid_note = || Error::InvalidNote(note_str.to_string());
+        let mut chars = note_str.chars();
+
+        let first = chars.next().ok_or_else(invalid_note)?.to_ascii_uppercase();
+        let letter = LETTERS
+            .iter()
+            .position(|&l| l == first)
+            .ok_or_else(invalid_note)? as Letter;
+
+        let accidental = match chars.as_str() {
+            "" => 0,
+            "#" => 1,
+            "##" | "x" => 2,
+            "b" => -1,
+            "bb" => -2,
+            _ => return Err(invalid_note()),
+        };
+
+        Ok(Note { letter, accidental })
+    }
+}
+
+/// Double sharps and flats are written `##` and `bb`.
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = if self.accidental < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{}{}",
+            LETTERS[self.letter as usize],
+            accidental.repeat(self.accidental.unsigned_abs() as usize)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_accepts_double_accidentals() {
+        assert_eq!(note("F##").pitch_class(), note("G").pitch_class());
+        assert_eq!(note("Fx"), note("F##"));
+        assert_eq!(note("Bbb").pitch_class(), note("A").pitch_class());
+        assert_eq!(note("Cb").pitch_class(), note("B").pitch_class());
+        assert_eq!(note("E#").pitch_class(), note("F").pitch_class());
+        assert_eq!(note("e#"), note("E#"));
+    }
+
+    #[test]
+    fn parse_rejects_bad_notes() {
+        for bad in ["", "H", "F#b", "Fxx", "B###", "Bbbb", "C♯", "#"].iter() {
+            assert_eq!(
+                bad.parse::<Note>(),
+                Err(Error::InvalidNote(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["C", "F#", "Bb", "E#", "Cb", "F##", "Bbb"].iter() {
+            assert_eq!(note(s).to_string(), *s);
+        }
+        assert_eq!(note("Gx").to_string(), "G##");
+    }
+
+    #[test]
+    fn up_keeps_the_letter_distance() {
+        assert_eq!(note("F#").up(6, 11), note("E#"));
+        assert_eq!(note("C").up(6, 9), note("Bbb"));
+        assert_eq!(note("G#").up(6, 11), note("F##"));
+        assert_eq!(note("Db").up(3, 5), note("Gb"));
+        assert_eq!(note("C").up(1, 14), note("D"));
+    }
+
+    #[test]
+    fn from_pitch_class_uses_one_accidental() {
+        assert_eq!(Note::from_pitch_class(1, false), note("A#"));
+        assert_eq!(Note::from_pitch_class(1, true), note("Bb"));
+        assert_eq!(Note::from_pitch_class(3, true), note("C"));
+        assert_eq!(Note::from_pitch_class(11, false), note("G#"));
+        assert_eq!(Note::from_pitch_class(11, true), note("Ab"));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{Error, Interval, Note, ScaleResult};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// 5-limit just ratios for each number of half steps above the tonic
+const JUST_RATIOS: [f64; 12] = [
+    1.0,
+    16.0 / 15.0,
+    9.0 / 8.0,
+    6.0 / 5.0,
+    5.0 / 4.0,
+    4.0 / 3.0,
+    45.0 / 32.0,
+    3.0 / 2.0,
+    8.0 / 5.0,
+    5.0 / 3.0,
+    9.0 / 5.0,
+    15.0 / 8.0,
+];
+
+const A4_MIDI: i16 = 69;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Temperament {
+    Equal,
+    /// Notes are tuned by whole number ratios to the tonic, which is itself
+    /// tuned in equal temperament.
+    Just {
+        tonic: Note,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    pub a4: f64,
+    pub temperament: Temperament,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning {
+            a4: 440.0,
+            temperament: Temperament::Equal,
+        }
+    }
+}
+
+/// A note in a given octave, in scientific pitch notation where middle C is C4.
+/// Octaves are numbered from C, so B#3 sounds the same as C4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pitch {
+    note: Note,
+    octave: i8,
+}
+
+impl Pitch {
+    pub fn new(note: Note, octave: i8) -> Self {
+        Pitch { note, octave }
+    }
+
+    pub fn note(self) -> Note {
+        self.note
+    }
+
+    pub fn octave(self) -> i8 {
+        self.octave
+    }
+
+    /// The pitch spelled as `note` that sounds at the MIDI number `number`,
+    /// even outside of 0 through 127, or an error if its octave doesn't fit in an `i8`.
+    /// O(1)
+    pub(crate) fn with_number(note: Note, number: i16) -> ScaleResult<Self> {
+        let octave = (number - note.above_c() as i16).div_euclid(12) - 1;
+        let octave = i8::try_from(octave).map_err(|_| Error::InvalidOctave(octave))?;
+        Ok(Pitch { note, octave })
+    }
+
+    /// Spells a MIDI note number with at most one sharp or flat
+    /// O(1)
+    pub fn from_midi(number: u8, flats: bool) -> Self {
+        let pitch_class = (number as i16 - A4_MIDI).rem_euclid(12) as u8;
+        // With one accidental at most, no note is spelled B# or Cb, so every note is in
+        // the octave that its C is in: -1 through 9
+        Pitch {
+            note: Note::from_pitch_class(pitch_class, flats),
+            octave: (number / 12) as i8 - 1,
+        }
+    }
+
+    /// The MIDI number, which may fall outside of 0 through 127
+    /// O(1)
+    pub(crate) fn number(self) -> i16 {
+        12 * (self.octave as i16 + 1) + self.note.above_c() as i16
+    }
+
+    /// O(1)
+    pub fn midi(self) -> Option<u8> {
+        match self.number() {
+            n @ 0..=127 => Some(n as u8),
+            _ => None,
+        }
+    }
+
+    /// O(1)
+    pub fn frequency(self, tuning: &Tuning) -> f64 {
+        let equal = |number: i16| tuning.a4 * 2f64.powf((number - A4_MIDI) as f64 / 12.0);
+        match tuning.temperament {
+            Temperament::Equal => equal(self.number()),
+            Temperament::Just { tonic } => {
+                let above_tonic =
+                    (self.note.pitch_class() as i16 - tonic.pitch_class() as i16).rem_euclid(12);
+                equal(self.number() - above_tonic) * JUST_RATIOS[above_tonic as usize]
+            }
+        }
+    }
+
+    /// The pitch `interval` higher, or an error if its octave doesn't fit in an `i8`
+    /// O(1)
+    pub fn transpose(self, interval: Interval) -> ScaleResult<Self> {
+        Pitch::with_number(
+            self.note.up(interval.letters(), interval.half_steps()),
+            self.number() + interval.half_steps() as i16,
+        )
+    }
+}
+
+/// Parses a note followed by its octave, like `C#4` or `Bb-1`
+impl FromStr for Pitch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> ScaleResult<Self> {
+        let invalid = || Error::InvalidNote(s.to_string());
+        let split = s
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '-' || c.is_ascii_digit())
+            .map(|(i, _)| i)
+            .ok_or_else(invalid)?;
+        let (note, octave) = s.split_at(split);
+
+        let note = note.parse().map_err(|_| invalid())?;
+        let octave = octave.parse().map_err(|_| invalid())?;
+        Ok(Pitch { note, octave })
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.note, self.octave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pitch(s: &str) -> Pitch {
+        s.parse().unwrap()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn parse_and_display_work() {
+        for s in ["C4", "C#4", "Bb-1", "F##7", "A0"].iter() {
+            assert_eq!(pitch(s).to_string(), *s);
+        }
+        for bad in ["", "C", "4", "H4", "C#", "C4.5"].iter() {
+            assert_eq!(
+                bad.parse::<Pitch>(),
+                Err(Error::InvalidNote(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn midi_works() {
+        assert_eq!(pitch("C4").midi(), Some(60));
+        assert_eq!(pitch("A4").midi(), Some(69));
+        assert_eq!(pitch("C-1").midi(), Some(0));
+        assert_eq!(pitch("G9").midi(), Some(127));
+        assert_eq!(pitch("B#3").midi(), Some(60));
+        assert_eq!(pitch("Cb4").midi(), Some(59));
+        assert_eq!(pitch("Cb-1").midi(), None);
+        assert_eq!(pitch("G#9").midi(), None);
+    }
+
+    #[test]
+    fn from_midi_works() {
+        assert_eq!(Pitch::from_midi(60, false), pitch("C4"));
+        assert_eq!(Pitch::from_midi(61, false), pitch("C#4"));
+        assert_eq!(Pitch::from_midi(61, true), pitch("Db4"));
+        assert_eq!(Pitch::from_midi(0, false), pitch("C-1"));
+        assert_eq!(Pitch::from_midi(127, true), pitch("G9"));
+        for n in 0..=127 {
+            assert_eq!(Pitch::from_midi(n, n % 2 == 0).midi(), Some(n));
+        }
+    }
+
+    #[test]
+    fn equal_temperament_frequencies() {
+        let tuning = Tuning::default();
+        assert!(close(pitch("A4").frequency(&tuning), 440.0));
+        assert!(close(pitch("A5").frequency(&tuning), 880.0));
+        assert!(close(pitch("A3").frequency(&tuning), 220.0));
+        assert!(close(pitch("C4").frequency(&tuning), 261.63));
+        assert!(close(pitch("B#3").frequency(&tuning), 261.63));
+
+        let baroque = Tuning {
+            a4: 415.0,
+            ..Tuning::default()
+        };
+        assert!(close(pitch("A4").frequency(&baroque), 415.0));
+    }
+
+    #[test]
+    fn just_intonation_frequencies() {
+        let tuning = Tuning {
+            a4: 440.0,
+            temperament: Temperament::Just {
+                tonic: "C".parse().unwrap(),
+            },
+        };
+        let c4 = 261.6256;
+        assert!(close(pitch("C4").frequency(&tuning), c4));
+        assert!(close(pitch("E4").frequency(&tuning), c4 * 5.0 / 4.0));
+        assert!(close(pitch("G4").frequency(&tuning), c4 * 3.0 / 2.0));
+        assert!(close(pitch("A4").frequency(&tuning), c4 * 5.0 / 3.0));
+        assert!(close(pitch("C5").frequency(&tuning), c4 * 2.0));
+        assert!(close(pitch("B3").frequency(&tuning), c4 * 15.0 / 16.0));
+    }
+
+    #[test]
+    fn transpose_works() {
+        let transpose = |p: &str, interval| pitch(p).transpose(interval).unwrap();
+        assert_eq!(transpose("C4", Interval::MAJOR_THIRD), pitch("E4"));
+        assert_eq!(transpose("B3", Interval::MINOR_SECOND), pitch("C4"));
+        assert_eq!(transpose("G4", Interval::PERFECT_FIFTH), pitch("D5"));
+        assert_eq!(transpose("E4", Interval::AUGMENTED_FOURTH), pitch("A#4"));
+        assert_eq!(transpose("A#3", Interval::MAJOR_SECOND), pitch("B#3"));
+        assert_eq!(transpose("C4", Interval::OCTAVE), pitch("C5"));
+        assert_eq!(transpose("C126", Interval::OCTAVE), pitch("C127"));
+    }
+
+    #[test]
+    fn transpose_past_the_last_octave_fails() {
+        assert_eq!(
+            pitch("C127").transpose(Interval::OCTAVE),
+            Err(Error::InvalidOctave(128))
+        );
+        assert_eq!(
+            pitch("B127").transpose(Interval::MINOR_SECOND),
+            Err(Error::InvalidOctave(128))
+        );
+        assert_eq!(
+            pitch("G127").transpose(Interval::MAJOR_SECOND),
+            Ok(pitch("A127"))
+        );
+    }
+}
@@ -96,6 +96,8 @@ fn test_minor_scale_with_flats() {
 #[test]
 /// Dorian mode
 fn test_dorian_mode() {
+    process_interval_case("d", "MmMMMmM", &["D", "E", "F", "G", "A", "B", "C", "D"]);
+}
 
 #[test]
 /// Mixolydian mode
@@ -138,7 +140,7 @@ fn test_locrian_mode() {
 ///
 /// Note that this case introduces the augmented second interval (A)
 fn test_harmonic_minor() {
-    process_interval_case("d", "MmMMmAm", &["D", "E", "F", "G", "A", "Bb", "Db", "D"]);
+    process_interval_case("d", "MmMMmAm", &["D", "E", "F", "G", "A", "Bb", "C#", "D"]);
 }
 
 #[test]
@@ -171,6 +173,6 @@ fn test_enigmatic() {
     process_interval_case(
         "G",
         "mAMMMmm",
-        &["G", "G#", "B", "C#", "D#", "F", "F#", "G"],
+        &["G", "Ab", "B", "C#", "D#", "E#", "F#", "G"],
     );
 }